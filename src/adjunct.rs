@@ -0,0 +1,312 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::str::from_utf8;
+
+use crate::{
+    bytes_to_f64,
+    bytes_to_i16,
+    bytes_to_i32,
+    Component,
+    DataType,
+    Endianness,
+    ephemeris::{
+        parse_type5000_components,
+        TYPE5000_COORD_SYSTEM_LENGTH,
+        TYPE5000_COORD_SYSTEM_OFFSET,
+    },
+    Error,
+    Header,
+    Result,
+    Subrecord,
+    TypeCode,
+    ADJUNCT_HEADER_OFFSET,
+    ADJUNCT_HEADER_SIZE,
+};
+
+/// Represents the adjunct header fields for type 1000 files.
+#[derive(Clone, Debug)]
+pub struct Type1000Adjunct {
+    pub xstart: f64,
+    pub xdelta: f64,
+    pub xunits: i32,
+}
+
+/// Represents the adjunct header fields for type 2000 files.
+#[derive(Clone, Debug)]
+pub struct Type2000Adjunct {
+    pub xstart: f64,
+    pub xdelta: f64,
+    pub xunits: i32,
+    pub subsize: i32,
+    pub ystart: f64,
+    pub ydelta: f64,
+    pub yunits: i32,
+}
+
+const TYPE3000_SUBRECORD_OFFSET: usize = 48;  // in bytes, from the start of the adjunct header
+const TYPE3000_SUBRECORD_SIZE: usize = 8;  // in bytes
+const TYPE3000_MAX_SUBRECORDS: usize = 26;
+
+/// Represents the adjunct header fields for type 3000 (record) files.
+#[derive(Clone, Debug)]
+pub struct Type3000Adjunct {
+    pub rstart: f64,
+    pub rdelta: f64,
+    pub runits: i32,
+    pub subrecords: i32,
+    pub r2start: f64,
+    pub r2delta: f64,
+    pub r2units: i32,
+    pub record_length: i32,
+
+    /// Names, formats and offsets of the fields in each record.
+    pub subrecord_table: Vec<Subrecord>,
+}
+
+/// Represents the adjunct header fields for type 4000 (keyword/value) files.
+#[derive(Clone, Debug)]
+pub struct Type4000Adjunct {
+    pub vrstart: f64,
+    pub vrdelta: f64,
+    pub vrunits: i32,
+    pub nrecords: i32,
+    pub vr2start: f64,
+    pub vr2delta: f64,
+    pub vr2units: i32,
+    pub vrecord_length: i32,
+}
+
+/// Represents the adjunct header fields for type 5000 (ephemeris) files.
+#[derive(Clone, Debug)]
+pub struct Type5000Adjunct {
+    pub rstart: f64,
+    pub rdelta: f64,
+    pub runits: i32,
+    pub subrecords: i32,
+    pub r2start: f64,
+    pub r2delta: f64,
+    pub r2units: i32,
+    pub record_length: i32,
+
+    /// Descriptions of the state vector components in each record.
+    pub components: Vec<Component>,
+
+    /// Coordinate system of the positions (ECR, ECI, GEO, etc.).
+    pub coord_system: String,
+}
+
+/// Represents the adjunct header fields for type 6000 files.
+#[derive(Clone, Debug)]
+pub struct Type6000Adjunct {
+    pub rstart: f64,
+    pub rdelta: f64,
+    pub runits: i32,
+    pub subrecords: i32,
+    pub r2start: f64,
+    pub r2delta: f64,
+    pub r2units: i32,
+    pub record_length: i32,
+
+    /// Offset of the subrecord definitions from the start of the extended header in bytes.
+    pub subr_offset: i32,
+}
+
+/// The adjunct header of a file, chosen by its type code.
+#[derive(Clone, Debug)]
+pub enum Adjunct {
+    Type1000(Type1000Adjunct),
+    Type2000(Type2000Adjunct),
+    Type3000(Type3000Adjunct),
+    Type4000(Type4000Adjunct),
+    Type5000(Type5000Adjunct),
+    Type6000(Type6000Adjunct),
+}
+
+impl Adjunct {
+    /// The type family (1000, 2000, etc.) of the adjunct.
+    pub fn type_family(&self) -> TypeCode {
+        match self {
+            Adjunct::Type1000(_) => 1000,
+            Adjunct::Type2000(_) => 2000,
+            Adjunct::Type3000(_) => 3000,
+            Adjunct::Type4000(_) => 4000,
+            Adjunct::Type5000(_) => 5000,
+            Adjunct::Type6000(_) => 6000,
+        }
+    }
+}
+
+fn parse_type1000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type1000Adjunct> {
+    Ok(Type1000Adjunct{
+        xstart: bytes_to_f64(&data[0..8], endianness)?,
+        xdelta: bytes_to_f64(&data[8..16], endianness)?,
+        xunits: bytes_to_i32(&data[16..20], endianness)?,
+    })
+}
+
+fn parse_type2000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type2000Adjunct> {
+    Ok(Type2000Adjunct{
+        xstart: bytes_to_f64(&data[0..8], endianness)?,
+        xdelta: bytes_to_f64(&data[8..16], endianness)?,
+        xunits: bytes_to_i32(&data[16..20], endianness)?,
+        subsize: bytes_to_i32(&data[20..24], endianness)?,
+        ystart: bytes_to_f64(&data[24..32], endianness)?,
+        ydelta: bytes_to_f64(&data[32..40], endianness)?,
+        yunits: bytes_to_i32(&data[40..44], endianness)?,
+    })
+}
+
+/// Trims the space and null padding from a fixed length name.
+pub(crate) fn parse_name(v: &[u8]) -> Result<String> {
+    match from_utf8(v) {
+        Ok(x) => Ok(x.trim_end_matches([' ', '\0']).to_string()),
+        Err(_) => Err(Error::ByteConversionError),
+    }
+}
+
+fn parse_type3000_subrecords(data: &[u8], count: i32, endianness: Endianness) -> Result<Vec<Subrecord>> {
+    if count < 0 || count as usize > TYPE3000_MAX_SUBRECORDS {
+        return Err(Error::InvalidSubrecordCount(count));
+    }
+
+    let mut subrecords = Vec::with_capacity(count as usize);
+
+    for i in 0..count as usize {
+        let start = TYPE3000_SUBRECORD_OFFSET + i*TYPE3000_SUBRECORD_SIZE;
+        let v = &data[start..start+TYPE3000_SUBRECORD_SIZE];
+        let offset = bytes_to_i16(&v[6..8], endianness)?;
+
+        if offset < 0 {
+            return Err(Error::InvalidSubrecordError(parse_name(&v[0..4])?));
+        }
+
+        subrecords.push(Subrecord{
+            name: parse_name(&v[0..4])?,
+            data_type: DataType{rank: v[4], format: v[5]},
+            offset: offset as usize,
+            num_elts: 1,
+        });
+    }
+
+    Ok(subrecords)
+}
+
+fn parse_type3000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type3000Adjunct> {
+    let subrecords = bytes_to_i32(&data[20..24], endianness)?;
+
+    Ok(Type3000Adjunct{
+        rstart: bytes_to_f64(&data[0..8], endianness)?,
+        rdelta: bytes_to_f64(&data[8..16], endianness)?,
+        runits: bytes_to_i32(&data[16..20], endianness)?,
+        subrecords,
+        r2start: bytes_to_f64(&data[24..32], endianness)?,
+        r2delta: bytes_to_f64(&data[32..40], endianness)?,
+        r2units: bytes_to_i32(&data[40..44], endianness)?,
+        record_length: bytes_to_i32(&data[44..48], endianness)?,
+        subrecord_table: parse_type3000_subrecords(data, subrecords, endianness)?,
+    })
+}
+
+fn parse_type4000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type4000Adjunct> {
+    Ok(Type4000Adjunct{
+        vrstart: bytes_to_f64(&data[0..8], endianness)?,
+        vrdelta: bytes_to_f64(&data[8..16], endianness)?,
+        vrunits: bytes_to_i32(&data[16..20], endianness)?,
+        nrecords: bytes_to_i32(&data[20..24], endianness)?,
+        vr2start: bytes_to_f64(&data[24..32], endianness)?,
+        vr2delta: bytes_to_f64(&data[32..40], endianness)?,
+        vr2units: bytes_to_i32(&data[40..44], endianness)?,
+        vrecord_length: bytes_to_i32(&data[44..48], endianness)?,
+    })
+}
+
+fn parse_type5000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type5000Adjunct> {
+    let subrecords = bytes_to_i32(&data[20..24], endianness)?;
+    let coord_system = &data[TYPE5000_COORD_SYSTEM_OFFSET..TYPE5000_COORD_SYSTEM_OFFSET+TYPE5000_COORD_SYSTEM_LENGTH];
+
+    Ok(Type5000Adjunct{
+        rstart: bytes_to_f64(&data[0..8], endianness)?,
+        rdelta: bytes_to_f64(&data[8..16], endianness)?,
+        runits: bytes_to_i32(&data[16..20], endianness)?,
+        subrecords,
+        r2start: bytes_to_f64(&data[24..32], endianness)?,
+        r2delta: bytes_to_f64(&data[32..40], endianness)?,
+        r2units: bytes_to_i32(&data[40..44], endianness)?,
+        record_length: bytes_to_i32(&data[44..48], endianness)?,
+        components: parse_type5000_components(data, subrecords)?,
+        coord_system: parse_name(coord_system)?,
+    })
+}
+
+fn parse_type6000_adjunct(data: &[u8], endianness: Endianness) -> Result<Type6000Adjunct> {
+    Ok(Type6000Adjunct{
+        rstart: bytes_to_f64(&data[0..8], endianness)?,
+        rdelta: bytes_to_f64(&data[8..16], endianness)?,
+        runits: bytes_to_i32(&data[16..20], endianness)?,
+        subrecords: bytes_to_i32(&data[20..24], endianness)?,
+        r2start: bytes_to_f64(&data[24..32], endianness)?,
+        r2delta: bytes_to_f64(&data[32..40], endianness)?,
+        r2units: bytes_to_i32(&data[40..44], endianness)?,
+        record_length: bytes_to_i32(&data[44..48], endianness)?,
+        subr_offset: bytes_to_i32(&data[48..52], endianness)?,
+    })
+}
+
+/// Parses the adjunct header from raw bytes, choosing the layout from the header's type code.
+pub fn parse_adjunct(data: &[u8], header: &Header) -> Result<Adjunct> {
+    if data.len() < ADJUNCT_HEADER_SIZE {
+        return Err(Error::NotEnoughAdjunctHeaderBytes(data.len()));
+    }
+
+    let endianness = header.header_endianness;
+
+    match header.type_code / 1000 {
+        1 => Ok(Adjunct::Type1000(parse_type1000_adjunct(data, endianness)?)),
+        2 => Ok(Adjunct::Type2000(parse_type2000_adjunct(data, endianness)?)),
+        3 => Ok(Adjunct::Type3000(parse_type3000_adjunct(data, endianness)?)),
+        4 => Ok(Adjunct::Type4000(parse_type4000_adjunct(data, endianness)?)),
+        5 => Ok(Adjunct::Type5000(parse_type5000_adjunct(data, endianness)?)),
+        6 => Ok(Adjunct::Type6000(parse_type6000_adjunct(data, endianness)?)),
+        _ => Err(Error::UnknownFileTypeCode(header.type_code)),
+    }
+}
+
+fn read_adjunct_bytes<R: Read + Seek>(mut reader: R) -> Result<Vec<u8>> {
+    match reader.seek(SeekFrom::Start(ADJUNCT_HEADER_OFFSET as u64)) {
+        Ok(x) => x,
+        Err(_) => return Err(Error::AdjunctHeaderSeekError),
+    };
+
+    let mut data = vec![0_u8; ADJUNCT_HEADER_SIZE];
+    let n = match reader.read(&mut data) {
+        Ok(x) => x,
+        Err(_) => return Err(Error::FileReadError),
+    };
+
+    if n < ADJUNCT_HEADER_SIZE {
+        return Err(Error::NotEnoughAdjunctHeaderBytes(n))
+    }
+
+    Ok(data)
+}
+
+/// Reads the adjunct header, choosing the layout from the header's type code.
+pub fn read_adjunct<R: Read + Seek>(reader: R, header: &Header) -> Result<Adjunct> {
+    let data = read_adjunct_bytes(reader)?;
+    parse_adjunct(&data, header)
+}
+
+/// Reads the adjunct header from a type 1000 file.  Prefer `read_adjunct` when the type of the
+/// file isn't known ahead of time.
+pub fn read_type1000_adjunct_header<R: Read + Seek>(reader: R, header: &Header) -> Result<Type1000Adjunct> {
+    let data = read_adjunct_bytes(reader)?;
+    parse_type1000_adjunct(&data, header.header_endianness)
+}
+
+/// Reads the adjunct header from a type 2000 file.  Prefer `read_adjunct` when the type of the
+/// file isn't known ahead of time.
+pub fn read_type2000_adjunct_header<R: Read + Seek>(reader: R, header: &Header) -> Result<Type2000Adjunct> {
+    let data = read_adjunct_bytes(reader)?;
+    parse_type2000_adjunct(&data, header.header_endianness)
+}
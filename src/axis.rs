@@ -0,0 +1,136 @@
+use crate::{
+    Error,
+    Header,
+    Result,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Units,
+};
+
+/// A regularly spaced axis, such as the x axis of a type 1000 file or the x and y axes of a type
+/// 2000 file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Axis {
+    pub start: f64,
+    pub delta: f64,
+
+    /// Number of points on the axis.
+    pub len: usize,
+
+    /// Units of the axis values.
+    pub units: Units,
+}
+
+impl Axis {
+    /// Value of the axis at point `n`.
+    pub fn value(&self, n: usize) -> f64 {
+        self.start + (n as f64)*self.delta
+    }
+
+    /// Creates an iterator over the values of the axis.
+    pub fn iter(&self) -> AxisIter<'_> {
+        AxisIter{
+            axis: self,
+            next: 0,
+        }
+    }
+
+    /// All of the values of the axis.
+    pub fn values(&self) -> Vec<f64> {
+        self.iter().collect()
+    }
+}
+
+/// Iterates over the values of an axis in order.
+pub struct AxisIter<'a> {
+    axis: &'a Axis,
+    next: usize,
+}
+
+impl Iterator for AxisIter<'_> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.axis.len {
+            return None;
+        }
+
+        self.next += 1;
+        Some(self.axis.value(self.next - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.axis.len - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for AxisIter<'_> {}
+
+impl<'a> IntoIterator for &'a Axis {
+    type Item = f64;
+    type IntoIter = AxisIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Number of whole elements of `bits` bits each in the data.  Counting in bits rather than bytes
+/// means the packed formats, which have no byte size, get an axis too.
+fn count_elements(header: &Header, bits: usize) -> Result<usize> {
+    let data_bits = (header.data_size as u64).saturating_mul(8);
+
+    match data_bits.checked_div(bits as u64) {
+        Some(x) => Ok(x as usize),
+        None => Err(Error::UnknownFormatError),
+    }
+}
+
+impl Type1000Adjunct {
+    /// The x axis, with one point per sample in the file.
+    pub fn x_axis(&self, header: &Header) -> Result<Axis> {
+        if header.type_code / 1000 != 1 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        Ok(Axis{
+            start: self.xstart,
+            delta: self.xdelta,
+            len: count_elements(header, header.data_type.num_bits()?)?,
+            units: self.x_units(),
+        })
+    }
+}
+
+impl Type2000Adjunct {
+    /// The x axis, with one point per bin of a frame.
+    pub fn x_axis(&self) -> Result<Axis> {
+        if self.subsize <= 0 {
+            return Err(Error::InvalidSubsizeError(self.subsize));
+        }
+
+        Ok(Axis{
+            start: self.xstart,
+            delta: self.xdelta,
+            len: self.subsize as usize,
+            units: self.x_units(),
+        })
+    }
+
+    /// The y axis, with one point per complete frame in the file.
+    pub fn y_axis(&self, header: &Header) -> Result<Axis> {
+        if header.type_code / 1000 != 2 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        let frame_bits = self.x_axis()?.len.saturating_mul(header.data_type.num_bits()?);
+
+        Ok(Axis{
+            start: self.ystart,
+            delta: self.ydelta,
+            len: count_elements(header, frame_bits)?,
+            units: self.y_units(),
+        })
+    }
+}
@@ -1,20 +1,21 @@
 use std::env;
-use std::fs::File;
 use std::path::PathBuf;
 use std::process::exit;
 
 use bluefile::{
+    Adjunct,
+    Bluefile,
+    Component,
     Error,
+    ExtKeyword,
     Header,
-    read_ext_header,
-    read_header,
-    read_type1000_adjunct_header,
-    read_type2000_adjunct_header,
     Result,
+    Subrecord,
+    Units,
+    Value,
 };
 
 struct Config {
-    file: File,
     path: PathBuf,
 }
 
@@ -36,63 +37,160 @@ fn get_config() -> Result<Config> {
     let mut path_buf = PathBuf::new();
     path_buf.push(path_str);
 
-    let file = match File::open(&path_buf) {
-        Ok(x) => x,
-        Err(_) => return Err(Error::FileOpenError(path_buf.display().to_string())),
-    };
-
     Ok(Config{
-        file,
         path: path_buf,
     })
 }
 
-fn header_lines(header: &Header, lines: &mut Vec<String>) {
+fn header_lines(bluefile: &mut Bluefile, lines: &mut Vec<String>) {
+    let timestamp = bluefile.timestamp();
+    let header = bluefile.header();
+
     lines.push(format!("  \"type_code\": \"{}\",", header.type_code));
     lines.push(format!("  \"header_endianness\": \"{}\",", header.header_endianness));
     lines.push(format!("  \"data_endianness\": \"{}\",", header.data_endianness));
+    lines.push(format!("  \"detached\": {},", header.detached));
+    lines.push(format!("  \"protected\": {},", header.protected));
+    lines.push(format!("  \"pipe\": {},", header.pipe));
     lines.push(format!("  \"ext_header_start\": {},", header.ext_start));
     lines.push(format!("  \"ext_header_size\": {},", header.ext_size));
     lines.push(format!("  \"data_start\": {},", header.data_start));
     lines.push(format!("  \"data_size\": {},", header.data_size));
     lines.push(format!("  \"data_type\": \"{}\",", header.data_type));
+    lines.push(format!("  \"flagmask\": {},", header.flagmask));
     lines.push(format!("  \"timecode\": {},", header.timecode));
+    lines.push(format!("  \"timecode_utc\": \"{}\",", timestamp));
+    lines.push(format!("  \"inlet\": {},", header.inlet));
+    lines.push(format!("  \"outlets\": {},", header.outlets));
+    lines.push(format!("  \"outmask\": {},", header.outmask));
+    lines.push(format!("  \"pipeloc\": {},", header.pipeloc));
+    lines.push(format!("  \"pipesize\": {},", header.pipesize));
+    lines.push(format!("  \"in_byte\": {},", header.in_byte));
+    lines.push(format!("  \"out_byte\": {},", header.out_byte));
+    let outbytes: Vec<String> = header.outbytes.iter().map(|x| x.to_string()).collect();
+    lines.push(format!("  \"outbytes\": [{}],", outbytes.join(", ")));
+    lines.push(format!("  \"keylength\": {},", header.keylength));
+}
+
+fn units_lines(name: &str, code: i32, lines: &mut Vec<String>) {
+    lines.push(format!("  \"{}\": {},", name, code));
+    lines.push(format!("  \"{}_name\": \"{}\",", name, Units::from(code)));
 }
 
-fn adjunct_lines(file: &File, header: &Header, lines: &mut Vec<String>) {
-    match header.type_code / 1000 {
-        1 => {
-            let adj = match read_type1000_adjunct_header(file, header) {
-                Ok(a) => a,
-                Err(_) => {
-                    println!("Error reading adjunct header");
-                    return;
-                }
-            };
+fn adjunct_lines(bluefile: &mut Bluefile, lines: &mut Vec<String>) {
+    let adjunct = match bluefile.adjunct() {
+        Ok(a) => a,
+        Err(_) => {
+            println!("Error reading adjunct header");
+            return;
+        }
+    };
 
+    match adjunct {
+        Adjunct::Type1000(adj) => {
             lines.push(format!("  \"xstart\": {},", adj.xstart));
             lines.push(format!("  \"xdelta\": {},", adj.xdelta));
-            lines.push(format!("  \"xunits\": {},", adj.xunits));
+            units_lines("xunits", adj.xunits, lines);
         },
-        2 => {
-            let adj = match read_type2000_adjunct_header(file, header) {
-                Ok(a) => a,
-                Err(_) => {
-                    println!("Error reading adjunct header");
-                    return;
-                }
-            };
-
+        Adjunct::Type2000(adj) => {
             lines.push(format!("  \"xstart\": {},", adj.xstart));
             lines.push(format!("  \"xdelta\": {},", adj.xdelta));
-            lines.push(format!("  \"xunits\": {},", adj.xunits));
+            units_lines("xunits", adj.xunits, lines);
             lines.push(format!("  \"subsize\": {},", adj.subsize));
             lines.push(format!("  \"ystart\": {},", adj.ystart));
             lines.push(format!("  \"ydelta\": {},", adj.ydelta));
-            lines.push(format!("  \"yunits\": {},", adj.yunits));
+            units_lines("yunits", adj.yunits, lines);
         },
-        _ => {},
+        Adjunct::Type3000(adj) => {
+            lines.push(format!("  \"rstart\": {},", adj.rstart));
+            lines.push(format!("  \"rdelta\": {},", adj.rdelta));
+            units_lines("runits", adj.runits, lines);
+            lines.push(format!("  \"subrecords\": {},", adj.subrecords));
+            lines.push(format!("  \"r2start\": {},", adj.r2start));
+            lines.push(format!("  \"r2delta\": {},", adj.r2delta));
+            units_lines("r2units", adj.r2units, lines);
+            lines.push(format!("  \"record_length\": {},", adj.record_length));
+            subrecord_lines(&adj.subrecord_table, lines);
+        },
+        Adjunct::Type4000(adj) => {
+            lines.push(format!("  \"vrstart\": {},", adj.vrstart));
+            lines.push(format!("  \"vrdelta\": {},", adj.vrdelta));
+            units_lines("vrunits", adj.vrunits, lines);
+            lines.push(format!("  \"nrecords\": {},", adj.nrecords));
+            lines.push(format!("  \"vr2start\": {},", adj.vr2start));
+            lines.push(format!("  \"vr2delta\": {},", adj.vr2delta));
+            units_lines("vr2units", adj.vr2units, lines);
+            lines.push(format!("  \"vrecord_length\": {},", adj.vrecord_length));
+        },
+        Adjunct::Type5000(adj) => {
+            lines.push(format!("  \"rstart\": {},", adj.rstart));
+            lines.push(format!("  \"rdelta\": {},", adj.rdelta));
+            units_lines("runits", adj.runits, lines);
+            lines.push(format!("  \"subrecords\": {},", adj.subrecords));
+            lines.push(format!("  \"r2start\": {},", adj.r2start));
+            lines.push(format!("  \"r2delta\": {},", adj.r2delta));
+            units_lines("r2units", adj.r2units, lines);
+            lines.push(format!("  \"record_length\": {},", adj.record_length));
+            lines.push(format!("  \"coord_system\": \"{}\",", adj.coord_system));
+            component_lines(&adj.components, lines);
+        },
+        Adjunct::Type6000(adj) => {
+            lines.push(format!("  \"rstart\": {},", adj.rstart));
+            lines.push(format!("  \"rdelta\": {},", adj.rdelta));
+            units_lines("runits", adj.runits, lines);
+            lines.push(format!("  \"subrecords\": {},", adj.subrecords));
+            lines.push(format!("  \"r2start\": {},", adj.r2start));
+            lines.push(format!("  \"r2delta\": {},", adj.r2delta));
+            units_lines("r2units", adj.r2units, lines);
+            lines.push(format!("  \"record_length\": {},", adj.record_length));
+            lines.push(format!("  \"subr_offset\": {},", adj.subr_offset));
+        },
+    }
+}
+
+fn subrecord_lines(subrecords: &[Subrecord], lines: &mut Vec<String>) {
+    if subrecords.is_empty() {
+        lines.push("  \"subrecord_table\": [],".to_string());
+        return;
     }
+
+    lines.push("  \"subrecord_table\": [".to_string());
+    let last_index = subrecords.len() - 1;
+
+    for (i, subrecord) in subrecords.iter().enumerate() {
+        let line = format!("    {{ \"name\": \"{}\", \"format\": \"{}\", \"offset\": {} }}", subrecord.name, subrecord.data_type, subrecord.offset);
+
+        if i == last_index {
+            lines.push(line);
+        } else {
+            lines.push(format!("{},", line));
+        }
+    }
+
+    lines.push("  ],".to_string());
+}
+
+fn component_lines(components: &[Component], lines: &mut Vec<String>) {
+    if components.is_empty() {
+        lines.push("  \"components\": [],".to_string());
+        return;
+    }
+
+    lines.push("  \"components\": [".to_string());
+    let last_index = components.len() - 1;
+
+    for (i, component) in components.iter().enumerate() {
+        let line = format!("    {{ \"name\": \"{}\", \"format\": \"{}\", \"kind\": \"{:?}\", \"units\": {} }}",
+            component.name, component.data_type, component.kind, component.units);
+
+        if i == last_index {
+            lines.push(line);
+        } else {
+            lines.push(format!("{},", line));
+        }
+    }
+
+    lines.push("  ],".to_string());
 }
 
 fn keyword_lines(header: &Header, lines: &mut Vec<String>) {
@@ -105,42 +203,110 @@ fn keyword_lines(header: &Header, lines: &mut Vec<String>) {
     let last_index = header.keywords.len() - 1;
 
     for (i, keyword) in header.keywords.iter().enumerate() {
+        let line = format!("    {{ \"name\": {}, \"value\": {} }}", json_string(&keyword.name), json_string(&keyword.value));
+
         if i == last_index {
-            lines.push(format!("    {{ \"name\": \"{}\", \"value\": \"{}\" }}", keyword.name, keyword.value));
+            lines.push(line);
         } else {
-            lines.push(format!("    {{ \"name\": \"{}\", \"value\": \"{}\" }},", keyword.name, keyword.value));
+            lines.push(format!("{},", line));
         }
     }
 
     lines.push("  ],".to_string());
 }
 
-fn ext_header_lines(file: &File, header: &Header, lines: &mut Vec<String>) {
-    let keywords = match read_ext_header(file, header) {
-        Ok(x) => x,
-        Err(_) => {
-            println!("Could not read extended header");
-            exit(1);
+/// Quotes a string for JSON, escaping quotes, backslashes and control characters.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a value for JSON.  JSON has no NaN or infinity, so those become null.
+fn json_value(value: &Value) -> String {
+    match value {
+        Value::F32(x) if !x.is_finite() => "null".to_string(),
+        Value::F64(x) if !x.is_finite() => "null".to_string(),
+        Value::ComplexF32(x) => format!("[{}, {}]", json_value(&Value::F32(x.re)), json_value(&Value::F32(x.im))),
+        Value::ComplexF64(x) => format!("[{}, {}]", json_value(&Value::F64(x.re)), json_value(&Value::F64(x.im))),
+        Value::String(x) => json_string(x),
+        Value::Array(x) => {
+            let items: Vec<String> = x.iter().map(json_value).collect();
+            format!("[{}]", items.join(", "))
         },
-    };
+        _ => value.to_string(),
+    }
+}
+
+fn ext_keyword_lines(name: &str, keywords: &[ExtKeyword], trailing_comma: bool, lines: &mut Vec<String>) {
+    let end = if trailing_comma { "," } else { "" };
 
     if keywords.is_empty() {
-        lines.push("  \"ext_header\": []".to_string());
+        lines.push(format!("  \"{}\": []{}", name, end));
         return;
     }
 
-    lines.push("  \"ext_header\": [".to_string());
+    lines.push(format!("  \"{}\": [", name));
     let last_index = keywords.len() - 1;
 
     for (i, keyword) in keywords.iter().enumerate() {
+        let value = match keyword.value.value() {
+            Ok(v) => json_value(&v),
+            Err(_) => json_string("?"),
+        };
+        let line = format!("    {{ \"name\": {}, \"value\": {}, \"format\": {} }}",
+            json_string(&keyword.tag), value, json_string(&keyword.value.format.to_string()));
+
         if i == last_index {
-            lines.push(format!("    {{ \"name\": \"{}\", \"value\": {}, \"format\": \"{}\" }}", keyword.tag, keyword.value, keyword.value.format));
+            lines.push(line);
         } else {
-            lines.push(format!("    {{ \"name\": \"{}\", \"value\": {}, \"format\": \"{}\" }},", keyword.tag, keyword.value, keyword.value.format));
+            lines.push(format!("{},", line));
         }
     }
 
-    lines.push("  ]".to_string());
+    lines.push(format!("  ]{}", end));
+}
+
+fn type4000_lines(bluefile: &mut Bluefile, lines: &mut Vec<String>) {
+    if bluefile.header().type_code / 1000 != 4 {
+        return;
+    }
+
+    let keywords = match bluefile.read_type4000_keywords() {
+        Ok(x) => x,
+        Err(_) => {
+            println!("Could not read type 4000 keywords");
+            exit(1);
+        },
+    };
+
+    ext_keyword_lines("values", &keywords, true, lines);
+}
+
+fn ext_header_lines(bluefile: &mut Bluefile, lines: &mut Vec<String>) {
+    let keywords = match bluefile.ext_header() {
+        Ok(x) => x,
+        Err(_) => {
+            println!("Could not read extended header");
+            exit(1);
+        },
+    };
+
+    ext_keyword_lines("ext_header", keywords, false, lines);
 }
 
 fn main() {
@@ -149,8 +315,12 @@ fn main() {
         Err(_) => exit(1),
     };
 
-    let header = match read_header(&config.file) {
-        Ok(h) => h,
+    let mut bluefile = match Bluefile::open(&config.path) {
+        Ok(b) => b,
+        Err(Error::FileOpenError(_)) => {
+            println!("Could not open {}", config.path.display());
+            exit(1);
+        },
         Err(_) => {
             println!("Could not read header from {}", config.path.display());
             exit(1);
@@ -158,10 +328,11 @@ fn main() {
     };
 
     let mut lines: Vec<String> = vec![];
-    header_lines(&header, &mut lines);
-    adjunct_lines(&config.file, &header, &mut lines);
-    keyword_lines(&header, &mut lines);
-    ext_header_lines(&config.file, &header, &mut lines);
+    header_lines(&mut bluefile, &mut lines);
+    adjunct_lines(&mut bluefile, &mut lines);
+    keyword_lines(bluefile.header(), &mut lines);
+    type4000_lines(&mut bluefile, &mut lines);
+    ext_header_lines(&mut bluefile, &mut lines);
     let all_lines = lines.join("\n");
 
     println!("{{");
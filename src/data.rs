@@ -0,0 +1,234 @@
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use crate::{
+    detached::check_attached,
+    Endianness,
+    Error,
+    Header,
+    Result,
+    Sample,
+    Type2000Adjunct,
+};
+
+/// Makes sure the data type in the header matches what is being read.  Returns the size of a
+/// single sample in bytes.
+pub(crate) fn check_data_type<T: Sample>(header: &Header) -> Result<usize> {
+    if header.data_type != T::data_type() {
+        return Err(Error::DataTypeMismatchError);
+    }
+
+    header.data_type.num_bytes()
+}
+
+/// Makes sure the file type and data type in the header match what is being read, and that the
+/// data is not detached.  Returns the size of a single sample in bytes.
+fn check_header<T: Sample>(header: &Header, type_family: i32) -> Result<usize> {
+    if header.type_code / 1000 != type_family {
+        return Err(Error::TypeCodeMismatchError);
+    }
+
+    check_attached(header)?;
+
+    check_data_type::<T>(header)
+}
+
+pub(crate) fn seek_data<R: Seek>(reader: &mut R, header: &Header) -> Result<()> {
+    match reader.seek(SeekFrom::Start(header.data_start as u64)) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::DataSeekError),
+    }
+}
+
+/// Reads exactly `length` bytes from the current position.  The buffer grows as the bytes arrive
+/// rather than being allocated up front, so a corrupt length from a header runs out of data
+/// instead of memory.
+pub(crate) fn read_bytes<R: Read>(reader: &mut R, length: usize) -> Result<Vec<u8>> {
+    let mut data = vec![];
+
+    match reader.take(length as u64).read_to_end(&mut data) {
+        Ok(n) if n == length => Ok(data),
+        _ => Err(Error::FileReadError),
+    }
+}
+
+/// Reads `count` samples of `sample_size` bytes each from the current position.
+pub(crate) fn read_samples<T: Sample, R: Read>(reader: &mut R, count: usize, sample_size: usize, endianness: Endianness) -> Result<Vec<T>> {
+    let length = match count.checked_mul(sample_size) {
+        Some(x) => x,
+        None => return Err(Error::FileReadError),
+    };
+    let data = read_bytes(reader, length)?;
+
+    data.chunks_exact(sample_size)
+        .map(|v| T::from_bytes(v, endianness))
+        .collect()
+}
+
+/// Reads all of the samples from a type 1000 file.
+pub fn read_type1000_data<T: Sample, R: Read + Seek>(mut reader: R, header: &Header) -> Result<Vec<T>> {
+    let sample_size = check_header::<T>(header, 1)?;
+    let count = (header.data_size as usize) / sample_size;
+    seek_data(&mut reader, header)?;
+    read_samples(&mut reader, count, sample_size, header.data_endianness)
+}
+
+/// Creates an iterator over the samples of a type 1000 file.
+pub fn iter_type1000_data<T: Sample, R: Read + Seek>(mut reader: R, header: &Header) -> Result<Type1000Iter<R, T>> {
+    let sample_size = check_header::<T>(header, 1)?;
+    seek_data(&mut reader, header)?;
+
+    Ok(Type1000Iter{
+        reader: BufReader::new(reader),
+        buf: vec![0_u8; sample_size],
+        remaining: (header.data_size as usize) / sample_size,
+        endianness: header.data_endianness,
+        phantom: PhantomData,
+    })
+}
+
+/// Iterates over the samples of a type 1000 file, reading them as needed.
+pub struct Type1000Iter<R, T> {
+    reader: BufReader<R>,
+    buf: Vec<u8>,
+    remaining: usize,
+    endianness: Endianness,
+    phantom: PhantomData<T>,
+}
+
+impl<R: Read, T: Sample> Iterator for Type1000Iter<R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        if self.reader.read_exact(&mut self.buf).is_err() {
+            self.remaining = 0;
+            return Some(Err(Error::FileReadError));
+        }
+
+        self.remaining -= 1;
+        Some(T::from_bytes(&self.buf, self.endianness))
+    }
+}
+
+/// Reads frames of `subsize` samples from a type 2000 file, either sequentially or by frame index.
+pub struct Type2000Reader<R, T> {
+    reader: R,
+    data_start: u64,
+    endianness: Endianness,
+    sample_size: usize,
+    subsize: usize,
+    num_frames: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<R: Read + Seek, T: Sample> Type2000Reader<R, T> {
+    /// Creates a frame reader for a type 2000 file.
+    pub fn new(reader: R, header: &Header, adjunct: &Type2000Adjunct) -> Result<Self> {
+        let sample_size = check_header::<T>(header, 2)?;
+
+        if adjunct.subsize <= 0 {
+            return Err(Error::InvalidSubsizeError(adjunct.subsize));
+        }
+
+        let subsize = adjunct.subsize as usize;
+        let num_frames = (header.data_size as usize) / (subsize * sample_size);
+
+        Ok(Type2000Reader{
+            reader,
+            data_start: header.data_start as u64,
+            endianness: header.data_endianness,
+            sample_size,
+            subsize,
+            num_frames,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Number of samples in each frame.
+    pub fn subsize(&self) -> usize {
+        self.subsize
+    }
+
+    /// Number of complete frames in the file.
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    fn frame_bytes(&self) -> usize {
+        self.subsize * self.sample_size
+    }
+
+    fn seek_frame(&mut self, n: usize) -> Result<()> {
+        let offset = self.data_start + (n * self.frame_bytes()) as u64;
+
+        match self.reader.seek(SeekFrom::Start(offset)) {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::DataSeekError),
+        }
+    }
+
+    /// Reads the frame at index `n`.
+    pub fn read_frame(&mut self, n: usize) -> Result<Vec<T>> {
+        if n >= self.num_frames {
+            return Err(Error::FrameOutOfRangeError(n));
+        }
+
+        self.seek_frame(n)?;
+        read_samples(&mut self.reader, self.subsize, self.sample_size, self.endianness)
+    }
+
+    /// Reads a contiguous range of frames.
+    pub fn read_frames(&mut self, range: Range<usize>) -> Result<Vec<Vec<T>>> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if range.end > self.num_frames {
+            return Err(Error::FrameOutOfRangeError(range.end - 1));
+        }
+
+        self.seek_frame(range.start)?;
+        let samples = read_samples(&mut self.reader, range.len() * self.subsize, self.sample_size, self.endianness)?;
+        Ok(samples.chunks_exact(self.subsize).map(|v| v.to_vec()).collect())
+    }
+
+    /// Creates an iterator over all of the frames in the file.
+    pub fn frames(&mut self) -> Type2000Frames<'_, R, T> {
+        Type2000Frames{
+            reader: self,
+            next_frame: 0,
+        }
+    }
+}
+
+/// Iterates over the frames of a type 2000 file in order.
+pub struct Type2000Frames<'a, R, T> {
+    reader: &'a mut Type2000Reader<R, T>,
+    next_frame: usize,
+}
+
+impl<R: Read + Seek, T: Sample> Iterator for Type2000Frames<'_, R, T> {
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_frame >= self.reader.num_frames() {
+            return None;
+        }
+
+        let frame = self.reader.read_frame(self.next_frame);
+        self.next_frame = match frame {
+            Ok(_) => self.next_frame + 1,
+            Err(_) => self.reader.num_frames(),
+        };
+
+        Some(frame)
+    }
+}
@@ -0,0 +1,45 @@
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::{
+    Error,
+    Header,
+    Result,
+};
+
+/// Extension X-Midas gives the data file of a detached header.
+const DETACHED_DATA_EXTENSION: &str = "det";
+
+/// Path of the data file that goes with a detached header.  Following the X-Midas convention, it
+/// is the header path with its extension replaced by `.det`.
+pub fn detached_data_path<P: AsRef<Path>>(header_path: P) -> PathBuf {
+    header_path.as_ref().with_extension(DETACHED_DATA_EXTENSION)
+}
+
+/// Makes sure the data described by the header is in the same source as the header.  The data of a
+/// detached header is in a separate file, so reading it from the header source would read the wrong
+/// bytes.
+pub(crate) fn check_attached(header: &Header) -> Result<()> {
+    if header.is_detached() {
+        return Err(Error::DetachedDataError);
+    }
+
+    Ok(())
+}
+
+/// Opens the file holding the data for a header read from `header_path`.  This is the companion
+/// `.det` file when the header is detached, or the header file itself otherwise.  The data readers
+/// reject detached headers, so read a detached file with `header.attached()`.
+pub fn open_data_file<P: AsRef<Path>>(header_path: P, header: &Header) -> Result<File> {
+    let path = if header.is_detached() {
+        detached_data_path(header_path)
+    } else {
+        header_path.as_ref().to_path_buf()
+    };
+
+    match File::open(&path) {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::FileOpenError(path.display().to_string())),
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    DataType,
+    Endianness,
+    Error,
+    Result,
+    Sample,
+    Scalar,
+};
+
+/// Decodes `N` consecutive components.
+fn decode_components<T: Scalar, const N: usize>(v: &[u8], endianness: Endianness) -> Result<[T; N]> {
+    let size = T::data_type().num_bytes()?;
+
+    if v.len() < N*size {
+        return Err(Error::ByteConversionError);
+    }
+
+    let components: Result<Vec<T>> = v[0..N*size].chunks_exact(size)
+        .map(|x| T::from_bytes(x, endianness))
+        .collect();
+
+    match components?.try_into() {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::ByteConversionError),
+    }
+}
+
+/// Decodes `N` consecutive rows of `N` components each.
+fn decode_rows<T: Scalar, const N: usize>(v: &[u8], endianness: Endianness) -> Result<[[T; N]; N]> {
+    let size = T::data_type().num_bytes()?;
+
+    if v.len() < N*N*size {
+        return Err(Error::ByteConversionError);
+    }
+
+    let rows: Result<Vec<[T; N]>> = v[0..N*N*size].chunks_exact(N*size)
+        .map(|x| decode_components(x, endianness))
+        .collect();
+
+    match rows?.try_into() {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::ByteConversionError),
+    }
+}
+
+/// A three component vector (rank V).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector<T>(pub [T; 3]);
+
+/// A quaternion (rank Q), with its components in file order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quaternion<T>(pub [T; 4]);
+
+/// A 3x3 matrix (rank M), stored by row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix3<T>(pub [[T; 3]; 3]);
+
+/// A 4x4 matrix (rank T), stored by row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Matrix4<T>(pub [[T; 4]; 4]);
+
+impl<T: Scalar> Sample for Vector<T> {
+    fn data_type() -> DataType {
+        DataType{rank: b'V', format: T::data_type().format}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        Ok(Vector(decode_components(v, endianness)?))
+    }
+}
+
+impl<T: Scalar> Sample for Quaternion<T> {
+    fn data_type() -> DataType {
+        DataType{rank: b'Q', format: T::data_type().format}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        Ok(Quaternion(decode_components(v, endianness)?))
+    }
+}
+
+impl<T: Scalar> Sample for Matrix3<T> {
+    fn data_type() -> DataType {
+        DataType{rank: b'M', format: T::data_type().format}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        Ok(Matrix3(decode_rows(v, endianness)?))
+    }
+}
+
+impl<T: Scalar> Sample for Matrix4<T> {
+    fn data_type() -> DataType {
+        DataType{rank: b'T', format: T::data_type().format}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        Ok(Matrix4(decode_rows(v, endianness)?))
+    }
+}
+
+/// Multi-channel data (ranks 1-9), with one component per channel.  Only `N` of 1 to 9 matches a
+/// valid rank.
+impl<T: Scalar, const N: usize> Sample for [T; N] {
+    fn data_type() -> DataType {
+        let rank = if N <= 9 { b'0' + N as u8 } else { b'?' };
+        DataType{rank, format: T::data_type().format}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        decode_components(v, endianness)
+    }
+}
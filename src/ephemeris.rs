@@ -0,0 +1,183 @@
+use std::io::Read;
+use std::io::Seek;
+
+use crate::{
+    adjunct::parse_name,
+    DataType,
+    Error,
+    Header,
+    Record,
+    RecordReader,
+    Result,
+    Subrecord,
+    Type5000Adjunct,
+};
+
+// The X-Midas type 5000 adjunct describes up to 14 components of 8 bytes each (name, format, type
+// and units) after the common record fields.  The quadwords of frame parameters that follow are not
+// decoded, only the name of the coordinate frame after them.
+pub(crate) const TYPE5000_COMPONENT_OFFSET: usize = 48;  // in bytes, from the start of the adjunct header
+pub(crate) const TYPE5000_COMPONENT_SIZE: usize = 8;  // in bytes
+pub(crate) const TYPE5000_MAX_COMPONENTS: usize = 14;
+pub(crate) const TYPE5000_COORD_SYSTEM_OFFSET: usize = 248;  // in bytes, from the start of the adjunct header
+pub(crate) const TYPE5000_COORD_SYSTEM_LENGTH: usize = 4;  // in bytes
+
+/// What a component of a type 5000 record holds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComponentKind {
+    Other,
+    Time,
+    Position,
+    Velocity,
+    Acceleration,
+}
+
+impl From<u8> for ComponentKind {
+    fn from(v: u8) -> Self {
+        match v {
+            1 => ComponentKind::Time,
+            2 => ComponentKind::Position,
+            3 => ComponentKind::Velocity,
+            4 => ComponentKind::Acceleration,
+            _ => ComponentKind::Other,
+        }
+    }
+}
+
+impl From<ComponentKind> for u8 {
+    fn from(v: ComponentKind) -> Self {
+        match v {
+            ComponentKind::Other => 0,
+            ComponentKind::Time => 1,
+            ComponentKind::Position => 2,
+            ComponentKind::Velocity => 3,
+            ComponentKind::Acceleration => 4,
+        }
+    }
+}
+
+/// Describes one component of the state vectors in a type 5000 file.  Components are packed into
+/// each record in the order they are described.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component {
+    pub name: String,
+    pub data_type: DataType,
+    pub kind: ComponentKind,
+    pub units: u8,
+}
+
+pub(crate) fn parse_type5000_components(data: &[u8], count: i32) -> Result<Vec<Component>> {
+    if count < 0 || count as usize > TYPE5000_MAX_COMPONENTS {
+        return Err(Error::InvalidSubrecordCount(count));
+    }
+
+    let mut components = Vec::with_capacity(count as usize);
+
+    for i in 0..count as usize {
+        let start = TYPE5000_COMPONENT_OFFSET + i*TYPE5000_COMPONENT_SIZE;
+        let v = &data[start..start+TYPE5000_COMPONENT_SIZE];
+
+        components.push(Component{
+            name: parse_name(&v[0..4])?,
+            data_type: DataType{rank: v[4], format: v[5]},
+            kind: ComponentKind::from(v[6]),
+            units: v[7],
+        });
+    }
+
+    Ok(components)
+}
+
+impl Type5000Adjunct {
+    /// Lays the components out as subrecords so the records can be read by name.
+    pub fn subrecord_table(&self) -> Result<Vec<Subrecord>> {
+        let mut offset = 0;
+        let mut subrecords = Vec::with_capacity(self.components.len());
+
+        for component in &self.components {
+            subrecords.push(Subrecord{
+                name: component.name.clone(),
+                data_type: component.data_type.clone(),
+                offset,
+                num_elts: 1,
+            });
+            offset += component.data_type.num_bytes()?;
+        }
+
+        Ok(subrecords)
+    }
+}
+
+/// A time-tagged position, with velocity and acceleration when the file has them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateVector {
+    /// Time of the state vector in the units of `rstart`/`rdelta`.
+    pub time: f64,
+    pub position: [f64; 3],
+    pub velocity: Option<[f64; 3]>,
+    pub acceleration: Option<[f64; 3]>,
+}
+
+fn component_triple(record: &Record, components: &[Component], kind: ComponentKind) -> Result<Option<[f64; 3]>> {
+    let names: Vec<&String> = components.iter()
+        .filter(|c| c.kind == kind)
+        .map(|c| &c.name)
+        .collect();
+
+    if names.is_empty() {
+        return Ok(None);
+    }
+
+    if names.len() != 3 {
+        return Err(Error::InvalidComponentsError(kind));
+    }
+
+    let mut triple = [0.0; 3];
+
+    for (i, name) in names.iter().enumerate() {
+        triple[i] = match record.get(name) {
+            Some(v) => v.as_f64()?,
+            None => return Err(Error::InvalidComponentsError(kind)),
+        };
+    }
+
+    Ok(Some(triple))
+}
+
+impl StateVector {
+    /// Builds a state vector from the record at index `n`.  The time comes from a time component
+    /// if there is one, otherwise from `rstart` and `rdelta`.
+    pub fn from_record(record: &Record, n: usize, adjunct: &Type5000Adjunct) -> Result<Self> {
+        let time = match adjunct.components.iter().find(|c| c.kind == ComponentKind::Time) {
+            Some(c) => match record.get(&c.name) {
+                Some(v) => v.as_f64()?,
+                None => return Err(Error::InvalidComponentsError(ComponentKind::Time)),
+            },
+            None => adjunct.rstart + (n as f64)*adjunct.rdelta,
+        };
+
+        let position = match component_triple(record, &adjunct.components, ComponentKind::Position)? {
+            Some(x) => x,
+            None => return Err(Error::InvalidComponentsError(ComponentKind::Position)),
+        };
+
+        Ok(StateVector{
+            time,
+            position,
+            velocity: component_triple(record, &adjunct.components, ComponentKind::Velocity)?,
+            acceleration: component_triple(record, &adjunct.components, ComponentKind::Acceleration)?,
+        })
+    }
+}
+
+/// Reads all of the state vectors from a type 5000 file.
+pub fn read_state_vectors<R: Read + Seek>(reader: R, header: &Header, adjunct: &Type5000Adjunct) -> Result<Vec<StateVector>> {
+    let mut reader = RecordReader::from_type5000(reader, header, adjunct)?;
+    let mut state_vectors = Vec::with_capacity(reader.num_records());
+
+    for (n, record) in reader.records().enumerate() {
+        state_vectors.push(StateVector::from_record(&record?, n, adjunct)?);
+    }
+
+    Ok(state_vectors)
+}
@@ -0,0 +1,252 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+#[cfg(feature = "notify")]
+use std::path::Path;
+#[cfg(feature = "notify")]
+use std::sync::mpsc::{channel, Receiver};
+
+#[cfg(feature = "notify")]
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    Adjunct,
+    data::check_data_type,
+    detached::check_attached,
+    Error,
+    Header,
+    read_adjunct,
+    read_header,
+    Result,
+    Sample,
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BATCH_BYTES: usize = 1 << 20;
+
+/// Reads a type 1000 or 2000 file while it is still being recorded, "tail -f" style.  The header
+/// is re-read to find out how much data there is, and only whole frames are handed out.  Frames
+/// are single samples for type 1000 files and `subsize` samples for type 2000 files.
+///
+/// A header that fails to parse, as can happen while the recorder is rewriting it at the end of a
+/// recording, is ignored until it reads back cleanly.  By default the reader checks for new data
+/// every 100 ms.  With the `notify` feature it can also be woken by file change notifications.
+/// Each read hands out at most 1 MiB of frames by default, leaving the rest for the next read.
+pub struct FollowReader<R, T> {
+    reader: R,
+    header: Header,
+    sample_size: usize,
+    frame_size: usize,
+    position: u64,
+    poll_interval: Duration,
+    idle_timeout: Option<Duration>,
+    max_batch_bytes: usize,
+    #[cfg(feature = "notify")]
+    watcher: Option<(RecommendedWatcher, Receiver<()>)>,
+    phantom: PhantomData<T>,
+}
+
+impl<R: Read + Seek, T: Sample> FollowReader<R, T> {
+    /// Creates a reader that starts at the beginning of the data.
+    pub fn new(mut reader: R, header: &Header) -> Result<Self> {
+        check_attached(header)?;
+        let sample_size = check_data_type::<T>(header)?;
+
+        let frame_size = match read_adjunct(&mut reader, header)? {
+            Adjunct::Type1000(_) => 1,
+            Adjunct::Type2000(adj) if adj.subsize > 0 => adj.subsize as usize,
+            Adjunct::Type2000(adj) => return Err(Error::InvalidSubsizeError(adj.subsize)),
+            _ => return Err(Error::TypeCodeMismatchError),
+        };
+
+        Ok(FollowReader{
+            reader,
+            header: header.clone(),
+            sample_size,
+            frame_size,
+            position: 0,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            idle_timeout: None,
+            max_batch_bytes: DEFAULT_MAX_BATCH_BYTES,
+            #[cfg(feature = "notify")]
+            watcher: None,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Sets how long to wait between checks for new data.
+    pub fn set_poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = poll_interval;
+    }
+
+    /// Sets how long the iterators wait for new data before ending.  With no idle timeout, which
+    /// is the default, they wait forever.
+    pub fn set_idle_timeout(&mut self, idle_timeout: Option<Duration>) {
+        self.idle_timeout = idle_timeout;
+    }
+
+    /// Sets the most data a single read hands out, in bytes.  A read always hands out at least one
+    /// whole frame when there is one, even if it is bigger than this.
+    pub fn set_max_batch_bytes(&mut self, max_batch_bytes: usize) {
+        self.max_batch_bytes = max_batch_bytes;
+    }
+
+    /// Wakes the reader as soon as the file at `path` changes, rather than at the next poll.
+    #[cfg(feature = "notify")]
+    pub fn watch<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        let (tx, rx) = channel();
+
+        let mut watcher = match notify::recommended_watcher(move |_| { let _ = tx.send(()); }) {
+            Ok(x) => x,
+            Err(_) => return Err(Error::FileWatchError),
+        };
+
+        if watcher.watch(path.as_ref(), RecursiveMode::NonRecursive).is_err() {
+            return Err(Error::FileWatchError);
+        }
+
+        self.watcher = Some((watcher, rx));
+        Ok(())
+    }
+
+    /// Header as of the last refresh.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Number of samples in each frame.
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    /// Number of samples read so far.
+    pub fn samples_read(&self) -> usize {
+        (self.position as usize) / self.sample_size
+    }
+
+    /// Re-reads the header to find out how much data has been recorded.  A header that does not
+    /// parse is assumed to be in the middle of being rewritten and is skipped.
+    pub fn refresh(&mut self) -> Result<()> {
+        if let Ok(header) = read_header(&mut self.reader) {
+            if header.data_type != self.header.data_type || header.type_code / 1000 != self.header.type_code / 1000 {
+                return Err(Error::DataTypeMismatchError);
+            }
+
+            if (header.data_size as u64) < self.position {
+                return Err(Error::DataTruncatedError(header.data_size as u64));
+            }
+
+            self.header = header;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the whole frames recorded since the last read, without waiting.  The samples of the
+    /// frames are returned in order.  Frames beyond the maximum batch size are left for the next
+    /// read.
+    pub fn read_new(&mut self) -> Result<Vec<T>> {
+        self.refresh()?;
+
+        let frame_bytes = (self.frame_size * self.sample_size) as u64;
+        let max_frames = (self.max_batch_bytes as u64 / frame_bytes).max(1);
+        let available = ((self.header.data_size as u64 - self.position) / frame_bytes).min(max_frames) * frame_bytes;
+
+        if available == 0 {
+            return Ok(vec![]);
+        }
+
+        let offset = self.header.data_start as u64 + self.position;
+        if self.reader.seek(SeekFrom::Start(offset)).is_err() {
+            return Err(Error::DataSeekError);
+        }
+
+        let mut data = vec![0_u8; available as usize];
+        if self.reader.read_exact(&mut data).is_err() {
+            // the header got ahead of the data, so try again later
+            return Ok(vec![]);
+        }
+
+        self.position += available;
+        data.chunks_exact(self.sample_size)
+            .map(|v| T::from_bytes(v, self.header.data_endianness))
+            .collect()
+    }
+
+    fn wait(&mut self) {
+        #[cfg(feature = "notify")]
+        if let Some((_, rx)) = &self.watcher {
+            let _ = rx.recv_timeout(self.poll_interval);
+            while rx.try_recv().is_ok() {}
+            return;
+        }
+
+        sleep(self.poll_interval);
+    }
+
+    /// Waits until at least one new frame has been recorded and reads all of the new frames.
+    /// Gives up with `FollowTimeoutError` after `timeout`, or waits forever if there is no
+    /// timeout.
+    pub fn wait_new(&mut self, timeout: Option<Duration>) -> Result<Vec<T>> {
+        let start = Instant::now();
+
+        loop {
+            let data = self.read_new()?;
+
+            if !data.is_empty() {
+                return Ok(data);
+            }
+
+            if let Some(t) = timeout {
+                if start.elapsed() >= t {
+                    return Err(Error::FollowTimeoutError);
+                }
+            }
+
+            self.wait();
+        }
+    }
+
+    /// Creates an iterator over frames as they are recorded.  It ends once the idle timeout passes
+    /// without a new frame.
+    pub fn frames(&mut self) -> FollowFrames<'_, R, T> {
+        FollowFrames{
+            reader: self,
+            pending: vec![],
+        }
+    }
+}
+
+/// Iterates over the frames of a file as they are recorded.
+pub struct FollowFrames<'a, R, T> {
+    reader: &'a mut FollowReader<R, T>,
+    pending: Vec<Vec<T>>,  // frames read but not yet handed out, in reverse order
+}
+
+impl<R: Read + Seek, T: Sample> Iterator for FollowFrames<'_, R, T> {
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(frame) = self.pending.pop() {
+            return Some(Ok(frame));
+        }
+
+        let idle_timeout = self.reader.idle_timeout;
+        let data = match self.reader.wait_new(idle_timeout) {
+            Ok(x) => x,
+            Err(Error::FollowTimeoutError) => return None,
+            Err(e) => return Some(Err(e)),
+        };
+
+        self.pending = data.chunks_exact(self.reader.frame_size)
+            .rev()
+            .map(|x| x.to_vec())
+            .collect();
+        self.pending.pop().map(Ok)
+    }
+}
@@ -0,0 +1,291 @@
+use std::fs::File;
+use std::io::Read;
+use std::io::Seek;
+use std::path::Path;
+
+use crate::{
+    Adjunct,
+    Axis,
+    detached_data_path,
+    Error,
+    ExtKeyword,
+    FollowReader,
+    Header,
+    KeywordTree,
+    PipeReader,
+    read_adjunct,
+    read_frame_time_range,
+    read_frame_timestamp_range,
+    read_ext_header,
+    read_header,
+    read_time_range,
+    read_timestamp_range,
+    read_type1000_data,
+    read_type4000_keywords,
+    read_type6000_subrecords,
+    RecordReader,
+    Result,
+    Sample,
+    Subrecord,
+    Timestamp,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Type2000Reader,
+};
+
+/// An open bluefile.  The main header is parsed when the file is opened, while the adjunct and
+/// extended headers are parsed the first time they are asked for and cached after that.
+pub struct Bluefile<R = File> {
+    reader: R,
+
+    /// Source of the data when the header is detached.
+    data_reader: Option<R>,
+    header: Header,
+    adjunct: Option<Adjunct>,
+    ext_header: Option<Vec<ExtKeyword>>,
+}
+
+fn open_file<P: AsRef<Path>>(path: P) -> Result<File> {
+    match File::open(path.as_ref()) {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::FileOpenError(path.as_ref().display().to_string())),
+    }
+}
+
+/// Picks the source of the data, which is a separate file when the header is detached, along with
+/// the header that describes the data in that source.
+fn data_source<'a, R>(reader: &'a mut R, data_reader: &'a mut Option<R>, header: &Header) -> Result<(&'a mut R, Header)> {
+    match data_reader {
+        Some(x) => Ok((x, header.attached())),
+        None if header.is_detached() => Err(Error::DetachedDataError),
+        None => Ok((reader, header.clone())),
+    }
+}
+
+impl Bluefile<File> {
+    /// Opens the bluefile at the given path and reads its main header.  When the header is
+    /// detached, the data is read from the companion `.det` file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut bluefile = Bluefile::from_reader(open_file(path.as_ref())?)?;
+
+        if bluefile.header.is_detached() {
+            bluefile.data_reader = Some(open_file(detached_data_path(path))?);
+        }
+
+        Ok(bluefile)
+    }
+
+    /// Opens a detached header along with its data file at an explicit path.
+    pub fn open_detached<P: AsRef<Path>, Q: AsRef<Path>>(header_path: P, data_path: Q) -> Result<Self> {
+        Bluefile::from_readers(open_file(header_path)?, open_file(data_path)?)
+    }
+}
+
+impl<R: Read + Seek> Bluefile<R> {
+    /// Reads the main header from any seekable source and takes ownership of it.  A detached
+    /// header has no data to read from here, so use `from_readers` for those.
+    pub fn from_reader(mut reader: R) -> Result<Self> {
+        let header = read_header(&mut reader)?;
+
+        Ok(Bluefile{
+            reader,
+            data_reader: None,
+            header,
+            adjunct: None,
+            ext_header: None,
+        })
+    }
+
+    /// Reads the main header from one source and takes the data from another, as with detached
+    /// headers.
+    pub fn from_readers(reader: R, data_reader: R) -> Result<Self> {
+        let mut bluefile = Bluefile::from_reader(reader)?;
+        bluefile.data_reader = Some(data_reader);
+        Ok(bluefile)
+    }
+
+    /// Main header of the file.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Adjunct header of the file, with its layout chosen by the type code.
+    pub fn adjunct(&mut self) -> Result<&Adjunct> {
+        let adjunct = match self.adjunct.take() {
+            Some(x) => x,
+            None => read_adjunct(&mut self.reader, &self.header)?,
+        };
+
+        Ok(self.adjunct.insert(adjunct))
+    }
+
+    /// Adjunct header of a type 1000 file.
+    pub fn type1000_adjunct(&mut self) -> Result<&Type1000Adjunct> {
+        match self.adjunct()? {
+            Adjunct::Type1000(x) => Ok(x),
+            _ => Err(Error::TypeCodeMismatchError),
+        }
+    }
+
+    /// Adjunct header of a type 2000 file.
+    pub fn type2000_adjunct(&mut self) -> Result<&Type2000Adjunct> {
+        match self.adjunct()? {
+            Adjunct::Type2000(x) => Ok(x),
+            _ => Err(Error::TypeCodeMismatchError),
+        }
+    }
+
+    /// The x axis of a type 1000 or 2000 file.  For type 1000 there is one point per sample, and
+    /// for type 2000 one point per bin of a frame.
+    pub fn x_axis(&mut self) -> Result<Axis> {
+        self.adjunct()?;
+
+        match &self.adjunct {
+            Some(Adjunct::Type1000(x)) => x.x_axis(&self.header),
+            Some(Adjunct::Type2000(x)) => x.x_axis(),
+            _ => Err(Error::TypeCodeMismatchError),
+        }
+    }
+
+    /// The y axis of a type 2000 file, with one point per frame.
+    pub fn y_axis(&mut self) -> Result<Axis> {
+        self.adjunct()?;
+
+        match &self.adjunct {
+            Some(Adjunct::Type2000(x)) => x.y_axis(&self.header),
+            _ => Err(Error::TypeCodeMismatchError),
+        }
+    }
+
+    /// Extended header keywords.
+    pub fn ext_header(&mut self) -> Result<&[ExtKeyword]> {
+        let keywords = match self.ext_header.take() {
+            Some(x) => x,
+            None => read_ext_header(&mut self.reader, &self.header)?,
+        };
+
+        Ok(self.ext_header.insert(keywords))
+    }
+
+    /// Extended header keywords grouped by the dotted parts of their tags.
+    pub fn keyword_tree(&mut self) -> Result<KeywordTree> {
+        Ok(KeywordTree::from_keywords(self.ext_header()?))
+    }
+
+    /// Absolute time of the start of the data, including the `TC_PREC` extended header keyword
+    /// when there is one.  Falls back to the header timecode alone when the extended header or
+    /// `TC_PREC` cannot be read.
+    pub fn timestamp(&mut self) -> Timestamp {
+        // a damaged extended header shouldn't hide the start time, so fall back to the timecode
+        if self.ext_header().is_err() {
+            return Timestamp::from_j1950(self.header.timecode);
+        }
+
+        let keywords = self.ext_header.as_deref().unwrap_or_default();
+
+        match Timestamp::from_header(&self.header, keywords) {
+            Ok(x) => x,
+            Err(_) => Timestamp::from_j1950(self.header.timecode),
+        }
+    }
+
+    /// Reads all of the samples from a type 1000 file.
+    pub fn read_type1000_data<T: Sample>(&mut self) -> Result<Vec<T>> {
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_type1000_data(reader, &header)
+    }
+
+    /// Creates a frame reader for a type 2000 file.
+    pub fn type2000_reader<T: Sample>(&mut self) -> Result<Type2000Reader<&mut R, T>> {
+        let adjunct = self.type2000_adjunct()?.clone();
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        Type2000Reader::new(reader, &header, &adjunct)
+    }
+
+    /// Reads the samples of a type 1000 file from the absolute time `t0` up to, but not including,
+    /// `t1`, in seconds since J1950.
+    pub fn read_time_range<T: Sample>(&mut self, t0: f64, t1: f64) -> Result<Vec<T>> {
+        let adjunct = self.type1000_adjunct()?.clone();
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_time_range(reader, &header, &adjunct, t0, t1)
+    }
+
+    /// Reads the frames of a type 2000 file from the absolute time `t0` up to, but not including,
+    /// `t1`, in seconds since J1950.
+    pub fn read_frame_time_range<T: Sample>(&mut self, t0: f64, t1: f64) -> Result<Vec<Vec<T>>> {
+        let adjunct = self.type2000_adjunct()?.clone();
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_frame_time_range(reader, &header, &adjunct, t0, t1)
+    }
+
+    /// Reads the samples of a type 1000 file from the timestamp `t0` up to, but not including,
+    /// `t1`, including the `TC_PREC` extended header keyword when there is one.  Unlike
+    /// `timestamp`, an extended header that can't be read produces an error.
+    pub fn read_timestamp_range<T: Sample>(&mut self, t0: Timestamp, t1: Timestamp) -> Result<Vec<T>> {
+        let adjunct = self.type1000_adjunct()?.clone();
+        self.ext_header()?;
+
+        let keywords = self.ext_header.as_deref().unwrap_or_default();
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_timestamp_range(reader, &header, keywords, &adjunct, t0, t1)
+    }
+
+    /// Reads the frames of a type 2000 file from the timestamp `t0` up to, but not including,
+    /// `t1`, including the `TC_PREC` extended header keyword when there is one.  Unlike
+    /// `timestamp`, an extended header that can't be read produces an error.
+    pub fn read_frame_timestamp_range<T: Sample>(&mut self, t0: Timestamp, t1: Timestamp) -> Result<Vec<Vec<T>>> {
+        let adjunct = self.type2000_adjunct()?.clone();
+        self.ext_header()?;
+
+        let keywords = self.ext_header.as_deref().unwrap_or_default();
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_frame_timestamp_range(reader, &header, keywords, &adjunct, t0, t1)
+    }
+
+    /// Reads the keyword/value pairs from a type 4000 file.
+    pub fn read_type4000_keywords(&mut self) -> Result<Vec<ExtKeyword>> {
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+        read_type4000_keywords(reader, &header)
+    }
+
+    /// Creates a record reader for a type 3000, 5000 or 6000 file.
+    pub fn record_reader(&mut self) -> Result<RecordReader<&mut R>> {
+        let adjunct = self.adjunct()?.clone();
+
+        // type 6000 subrecords are described in the extended header, which stays with the header
+        let subrecords = match &adjunct {
+            Adjunct::Type6000(adj) => {
+                let subrecords: Result<Vec<Subrecord>> = read_type6000_subrecords(&mut self.reader, &self.header, adj)?
+                    .iter()
+                    .map(|s| s.subrecord())
+                    .collect();
+                subrecords?
+            },
+            _ => vec![],
+        };
+
+        let (reader, header) = data_source(&mut self.reader, &mut self.data_reader, &self.header)?;
+
+        match adjunct {
+            Adjunct::Type3000(adj) => RecordReader::from_type3000(reader, &header, &adj),
+            Adjunct::Type5000(adj) => RecordReader::from_type5000(reader, &header, &adj),
+            Adjunct::Type6000(adj) => RecordReader::new(reader, &header, subrecords, adj.record_length),
+            _ => Err(Error::TypeCodeMismatchError),
+        }
+    }
+
+    /// Creates a reader that follows a type 1000 or 2000 file as it is recorded.
+    pub fn follow_reader<T: Sample>(&mut self) -> Result<FollowReader<&mut R, T>> {
+        FollowReader::new(&mut self.reader, &self.header)
+    }
+
+    /// Creates a reader for the circular buffer of a pipe file.
+    pub fn pipe_reader(&mut self) -> Result<PipeReader<&mut R>> {
+        PipeReader::new(&mut self.reader, &self.header)
+    }
+
+    /// Gives back the underlying header source.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
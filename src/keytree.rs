@@ -0,0 +1,134 @@
+use crate::{
+    ExtKeyword,
+    ExtKeywordValue,
+};
+
+const TAG_SEPARATOR: char = '.';
+
+/// Extended header keywords grouped into a tree by the dotted parts of their tags, so that
+/// `RF.FREQ` and `RF.BW` are both children of `RF`.  Children are kept in the order they were
+/// first seen, and every value of a duplicated tag is kept in file order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KeywordTree {
+    name: String,
+    values: Vec<ExtKeywordValue>,
+    children: Vec<KeywordTree>,
+}
+
+impl KeywordTree {
+    /// Creates an empty tree.
+    pub fn new() -> Self {
+        KeywordTree::default()
+    }
+
+    /// Builds a tree from flat keywords, such as the output of `read_ext_header`.
+    pub fn from_keywords(keywords: &[ExtKeyword]) -> Self {
+        let mut tree = KeywordTree::new();
+
+        for keyword in keywords {
+            tree.insert(&keyword.tag, keyword.value.clone());
+        }
+
+        tree
+    }
+
+    fn child(&self, name: &str) -> Option<&KeywordTree> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn child_mut(&mut self, name: &str) -> &mut KeywordTree {
+        let i = match self.children.iter().position(|c| c.name == name) {
+            Some(i) => i,
+            None => {
+                self.children.push(KeywordTree{name: name.to_string(), ..KeywordTree::default()});
+                self.children.len() - 1
+            },
+        };
+
+        &mut self.children[i]
+    }
+
+    /// Adds a value under a dotted tag.  A tag that is already present keeps its earlier values
+    /// and gains another.
+    pub fn insert(&mut self, tag: &str, value: ExtKeywordValue) {
+        let mut node = self;
+
+        for name in tag.split(TAG_SEPARATOR) {
+            node = node.child_mut(name);
+        }
+
+        node.values.push(value);
+    }
+
+    /// Last part of the tag of this node, which is empty for the root.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Values with a tag ending at this node, in file order.
+    pub fn values(&self) -> &[ExtKeywordValue] {
+        &self.values
+    }
+
+    /// Child nodes in the order they were first seen.
+    pub fn children(&self) -> &[KeywordTree] {
+        &self.children
+    }
+
+    /// Looks up the node at a dotted path, relative to this node.
+    pub fn subtree(&self, path: &str) -> Option<&KeywordTree> {
+        let mut node = self;
+
+        for name in path.split(TAG_SEPARATOR) {
+            node = node.child(name)?;
+        }
+
+        Some(node)
+    }
+
+    /// Looks up the value at a dotted path.  When the tag is duplicated, the first value in the
+    /// file is returned.
+    pub fn get(&self, path: &str) -> Option<&ExtKeywordValue> {
+        self.get_all(path).first()
+    }
+
+    /// Looks up every value at a dotted path, in file order.
+    pub fn get_all(&self, path: &str) -> &[ExtKeywordValue] {
+        match self.subtree(path) {
+            Some(node) => &node.values,
+            None => &[],
+        }
+    }
+
+    fn collect<'a>(&'a self, prefix: Option<&str>, entries: &mut Vec<(String, &'a ExtKeywordValue)>) {
+        for value in &self.values {
+            entries.push((prefix.unwrap_or("").to_string(), value));
+        }
+
+        for child in &self.children {
+            let tag = match prefix {
+                Some(p) => format!("{}{}{}", p, TAG_SEPARATOR, child.name),
+                None => child.name.clone(),
+            };
+            child.collect(Some(&tag), entries);
+        }
+    }
+
+    /// Every value in the tree with its dotted tag relative to this node, depth first.  Values of
+    /// a node come before those of its children.
+    pub fn entries(&self) -> Vec<(String, &ExtKeywordValue)> {
+        let mut entries = vec![];
+        self.collect(None, &mut entries);
+        entries
+    }
+
+    /// Flattens the tree back into keywords for writing, in the same order as `entries`.
+    /// Keywords sharing a prefix end up next to each other, so the order can differ from the
+    /// keywords the tree was built from.
+    pub fn to_keywords(&self) -> Vec<ExtKeyword> {
+        self.entries()
+            .into_iter()
+            .map(|(tag, value)| ExtKeyword::new(&tag, value.clone()))
+            .collect()
+    }
+}
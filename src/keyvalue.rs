@@ -0,0 +1,78 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+
+use crate::{
+    Adjunct,
+    ADJUNCT_HEADER_SIZE,
+    COMMON_HEADER_SIZE,
+    data::read_bytes,
+    DataType,
+    detached::check_attached,
+    Endianness,
+    Error,
+    ExtKeyword,
+    ext_keywords_to_bytes,
+    Header,
+    parse_keyword_blocks,
+    Result,
+    Type4000Adjunct,
+    write_adjunct,
+    write_header,
+};
+
+/// Reads the keyword/value pairs stored in the data section of a type 4000 file.
+pub fn read_type4000_keywords<R: Read + Seek>(mut reader: R, header: &Header) -> Result<Vec<ExtKeyword>> {
+    if header.type_code / 1000 != 4 {
+        return Err(Error::TypeCodeMismatchError);
+    }
+
+    check_attached(header)?;
+
+    if reader.seek(SeekFrom::Start(header.data_start as u64)).is_err() {
+        return Err(Error::DataSeekError);
+    }
+
+    let data = read_bytes(&mut reader, header.data_size as usize)?;
+    parse_keyword_blocks(&data, header.data_endianness)
+}
+
+/// Writes a type 4000 file holding the given keyword/value pairs as a single record.  The values
+/// must all have the given endianness.
+pub fn write_type4000<W: Write>(mut writer: W, keywords: &[ExtKeyword], endianness: Endianness) -> Result<()> {
+    if keywords.iter().any(|k| k.value.endianness != endianness) {
+        return Err(Error::InvalidEndianness);
+    }
+
+    let data = ext_keywords_to_bytes(keywords)?;
+
+    let header = Header{
+        header_endianness: endianness,
+        data_endianness: endianness,
+        data_start: (COMMON_HEADER_SIZE + ADJUNCT_HEADER_SIZE) as f64,
+        data_size: data.len() as f64,
+        type_code: 4000,
+        data_type: DataType{rank: b'S', format: b'B'},
+        ..Header::default()
+    };
+
+    let adjunct = Adjunct::Type4000(Type4000Adjunct{
+        vrstart: 0.0,
+        vrdelta: 1.0,
+        vrunits: 0,
+        nrecords: 1,
+        vr2start: 0.0,
+        vr2delta: 1.0,
+        vr2units: 0,
+        vrecord_length: data.len() as i32,
+    });
+
+    write_header(&mut writer, &header)?;
+    write_adjunct(&mut writer, &adjunct, endianness)?;
+
+    match writer.write_all(&data) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::FileWriteError),
+    }
+}
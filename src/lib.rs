@@ -1,17 +1,18 @@
 //! Experimental Rust library for handling X-Midas Bluefiles.
 //!
 //! ```no_run
-//! use std::fs::File;
-//! use bluefile::read_header;
+//! use bluefile::Bluefile;
 //!
-//! let file = File::open("/path/to/bluefile").unwrap();
-//! let header = read_header(&file).unwrap();
-//! println!("{}", header.type_code);
-//! println!("{}", header.data_type);
+//! let mut bluefile = Bluefile::open("/path/to/bluefile").unwrap();
+//! println!("{}", bluefile.header().type_code);
+//! println!("{}", bluefile.header().data_type);
+//!
+//! for keyword in bluefile.ext_header().unwrap() {
+//!     println!("{} = {}", keyword.tag, keyword.value);
+//! }
 //! ```
 
 use std::fmt;
-use std::fs::File;
 use std::io::Read;
 use std::io::Seek;
 use std::io::SeekFrom;
@@ -19,14 +20,142 @@ use std::str::from_utf8;
 
 use num::complex::Complex;
 
-const ADJUNCT_HEADER_OFFSET: usize = 256;
-const ADJUNCT_HEADER_SIZE: usize = 256;
+use vax::{
+    f32_to_vax_f,
+    f64_to_vax_d,
+    f64_to_vax_g,
+    vax_d_to_f64,
+    vax_f_to_f32,
+    vax_g_to_f64,
+};
+
+mod adjunct;
+mod axis;
+mod data;
+mod detached;
+mod element;
+mod ephemeris;
+mod follow;
+mod handle;
+mod keytree;
+mod keyvalue;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod packed;
+mod pipe;
+mod record;
+mod sample;
+mod time;
+mod timestamp;
+mod units;
+mod value;
+mod vax;
+mod write;
+
+pub use adjunct::{
+    Adjunct,
+    parse_adjunct,
+    read_adjunct,
+    read_type1000_adjunct_header,
+    read_type2000_adjunct_header,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Type3000Adjunct,
+    Type4000Adjunct,
+    Type5000Adjunct,
+    Type6000Adjunct,
+};
+pub use axis::{
+    Axis,
+    AxisIter,
+};
+pub use data::{
+    iter_type1000_data,
+    read_type1000_data,
+    Type1000Iter,
+    Type2000Frames,
+    Type2000Reader,
+};
+pub use detached::{
+    detached_data_path,
+    open_data_file,
+};
+pub use element::{
+    Matrix3,
+    Matrix4,
+    Quaternion,
+    Vector,
+};
+pub use ephemeris::{
+    Component,
+    ComponentKind,
+    read_state_vectors,
+    StateVector,
+};
+pub use follow::{
+    FollowFrames,
+    FollowReader,
+};
+pub use handle::Bluefile;
+pub use keytree::KeywordTree;
+pub use keyvalue::{
+    read_type4000_keywords,
+    write_type4000,
+};
+#[cfg(feature = "mmap")]
+pub use mmap::MappedBluefile;
+pub use packed::{
+    iter_packed_data,
+    PackedIter,
+    read_packed_data,
+};
+pub use pipe::{
+    PipeReader,
+    PipeSamples,
+};
+pub use record::{
+    Field,
+    read_type6000_subrecords,
+    Record,
+    RecordReader,
+    Records,
+    Subrecord,
+    Type6000Subrecord,
+};
+pub use sample::{
+    Sample,
+    Scalar,
+};
+pub use time::{
+    read_frame_time_range,
+    read_frame_timestamp_range,
+    read_time_range,
+    read_timestamp_range,
+};
+pub use timestamp::Timestamp;
+pub use units::Units;
+pub use value::Value;
+pub use write::{
+    adjunct_to_bytes,
+    ext_keyword_to_bytes,
+    ext_keywords_to_bytes,
+    header_to_bytes,
+    write_adjunct,
+    write_bluefile,
+    write_detached,
+    write_ext_header,
+    write_header,
+};
+
+pub(crate) const ADJUNCT_HEADER_OFFSET: usize = 256;
+pub(crate) const ADJUNCT_HEADER_SIZE: usize = 256;
 const EXT_KEYWORD_LENGTH: usize = 4;
+pub(crate) const EXT_KEYWORD_HEADER_LENGTH: usize = 8;  // lkey, lext, ltag & format
 
 const COMMON_HEADER_OFFSET: usize = 0;  // in bytes
-const COMMON_HEADER_SIZE: usize = 256;  // in bytes
-const HEADER_KEYWORD_OFFSET: usize = 164;  // in bytes
-const HEADER_KEYWORD_LENGTH: usize = 92;  // in bytes
+pub(crate) const COMMON_HEADER_SIZE: usize = 256;  // in bytes
+pub(crate) const HEADER_KEYWORD_OFFSET: usize = 164;  // in bytes
+pub(crate) const HEADER_KEYWORD_LENGTH: usize = 92;  // in bytes
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -37,14 +166,18 @@ pub enum Error {
     UnknownRankError,
     UnknownFormatError,
     UnknownDataTypeError,
+    DataTypeMismatchError,
+    PackedFormatError,
     InvalidEndianness,
     ByteConversionError,
     FileOpenError(String),
     FileReadError,
+    FileWriteError,
     NotEnoughHeaderBytes(usize),
     NotEnoughAdjunctHeaderBytes(usize),
     UnknownFileTypeCode(i32),
     InvalidHeaderKeywordLength(usize),
+    HeaderKeywordLengthMismatchError(usize, usize),
     HeaderSeekError,
     AdjunctHeaderSeekError,
     ExtHeaderSeekError,
@@ -52,7 +185,36 @@ pub enum Error {
     HeaderKeywordLengthParseError,
     ExtHeaderKeywordLengthParseError,
     ExtHeaderKeywordReadError,
+    ExtHeaderKeywordTagParseError,
+    InvalidExtKeywordTag(String),
+    InvalidExtStart(usize),
+    DetachedDataError,
+    NotAPipeError,
+    InvalidPipeSizeError(i32),
+    PipeOverrunError(u64),
+    PipeClosedError,
+    PipeTimeoutError,
+    PipeReadTooLargeError(usize),
+    FollowTimeoutError,
+    DataTruncatedError(u64),
+    FileWatchError,
     DataSeekError,
+    NotEnoughDataBytes(usize),
+    SampleOutOfRangeError(usize),
+    MemoryMapError,
+    InvalidSubsizeError(i32),
+    FrameOutOfRangeError(usize),
+    InvalidTimeDeltaError(f64),
+    NotTimeAxisError(Units),
+    TimeOutOfRangeError(f64),
+    TimestampParseError(String),
+    InvalidSubrecordCount(i32),
+    InvalidSubrecordError(String),
+    InvalidCoordSystemError(String),
+    InvalidRecordLengthError(i32),
+    RecordOutOfRangeError(usize),
+    InvalidComponentsError(ComponentKind),
+    ValueConversionError,
     BluejayConfigError,
 }
 
@@ -66,14 +228,18 @@ impl fmt::Display for Error {
             Error::UnknownRankError => f.write_str("UnknownRankError"),
             Error::UnknownFormatError => f.write_str("UnknownFormatError"),
             Error::UnknownDataTypeError => f.write_str("UnknownDataTypeError"),
+            Error::DataTypeMismatchError => f.write_str("DataTypeMismatchError"),
+            Error::PackedFormatError => f.write_str("PackedFormatError"),
             Error::InvalidEndianness => f.write_str("InvalidEndianness"),
             Error::ByteConversionError => f.write_str("ByteConversionError"),
             Error::FileOpenError(e) => write!(f, "FileOpenError: {}", e),
             Error::FileReadError => f.write_str("FileReadError"),
+            Error::FileWriteError => f.write_str("FileWriteError"),
             Error::NotEnoughHeaderBytes(n) => write!(f, "NotEnoughHeaderBytes: {} bytes of {}", n, COMMON_HEADER_SIZE),
             Error::NotEnoughAdjunctHeaderBytes(n) => write!(f, "NotEnoughAdjunctHeaderBytes: {} bytes of {}", n, ADJUNCT_HEADER_SIZE),
             Error::UnknownFileTypeCode(e) => write!(f, "UnknownFileTypeCode: {}", e),
             Error::InvalidHeaderKeywordLength(n) => write!(f, "InvalidHeaderKeywordLength: {} bytes of {} max", n, HEADER_KEYWORD_LENGTH),
+            Error::HeaderKeywordLengthMismatchError(n, m) => write!(f, "HeaderKeywordLengthMismatchError: keylength {} for {} bytes of keywords", n, m),
             Error::HeaderSeekError => f.write_str("HeaderSeekError"),
             Error::AdjunctHeaderSeekError => f.write_str("AdjunctHeaderSeekError"),
             Error::ExtHeaderSeekError => f.write_str("ExtHeaderSeekError"),
@@ -81,7 +247,36 @@ impl fmt::Display for Error {
             Error::HeaderKeywordLengthParseError => f.write_str("HeaderKeywordLengthParseError"),
             Error::ExtHeaderKeywordLengthParseError => f.write_str("ExtHeaderKeywordLengthParseError"),
             Error::ExtHeaderKeywordReadError => f.write_str("ExtHeaderKeywordReadError"),
+            Error::ExtHeaderKeywordTagParseError => f.write_str("ExtHeaderKeywordTagParseError"),
+            Error::InvalidExtKeywordTag(e) => write!(f, "InvalidExtKeywordTag: {}", e),
+            Error::InvalidExtStart(n) => write!(f, "InvalidExtStart: {} is not a multiple of 512", n),
+            Error::DetachedDataError => f.write_str("DetachedDataError"),
+            Error::NotAPipeError => f.write_str("NotAPipeError"),
+            Error::InvalidPipeSizeError(n) => write!(f, "InvalidPipeSizeError: {}", n),
+            Error::PipeOverrunError(n) => write!(f, "PipeOverrunError: {} bytes lost", n),
+            Error::PipeClosedError => f.write_str("PipeClosedError"),
+            Error::PipeTimeoutError => f.write_str("PipeTimeoutError"),
+            Error::PipeReadTooLargeError(n) => write!(f, "PipeReadTooLargeError: {} bytes", n),
+            Error::FollowTimeoutError => f.write_str("FollowTimeoutError"),
+            Error::DataTruncatedError(n) => write!(f, "DataTruncatedError: {} bytes", n),
+            Error::FileWatchError => f.write_str("FileWatchError"),
             Error::DataSeekError => f.write_str("DataSeekError"),
+            Error::NotEnoughDataBytes(n) => write!(f, "NotEnoughDataBytes: {} bytes", n),
+            Error::SampleOutOfRangeError(n) => write!(f, "SampleOutOfRangeError: {}", n),
+            Error::MemoryMapError => f.write_str("MemoryMapError"),
+            Error::InvalidSubsizeError(n) => write!(f, "InvalidSubsizeError: {}", n),
+            Error::FrameOutOfRangeError(n) => write!(f, "FrameOutOfRangeError: {}", n),
+            Error::InvalidTimeDeltaError(x) => write!(f, "InvalidTimeDeltaError: {}", x),
+            Error::NotTimeAxisError(units) => write!(f, "NotTimeAxisError: {}", units),
+            Error::TimeOutOfRangeError(t) => write!(f, "TimeOutOfRangeError: {}", t),
+            Error::TimestampParseError(e) => write!(f, "TimestampParseError: {}", e),
+            Error::InvalidSubrecordCount(n) => write!(f, "InvalidSubrecordCount: {}", n),
+            Error::InvalidSubrecordError(e) => write!(f, "InvalidSubrecordError: {}", e),
+            Error::InvalidCoordSystemError(e) => write!(f, "InvalidCoordSystemError: {}", e),
+            Error::InvalidRecordLengthError(n) => write!(f, "InvalidRecordLengthError: {}", n),
+            Error::RecordOutOfRangeError(n) => write!(f, "RecordOutOfRangeError: {}", n),
+            Error::InvalidComponentsError(k) => write!(f, "InvalidComponentsError: {:?}", k),
+            Error::ValueConversionError => f.write_str("ValueConversionError"),
             Error::BluejayConfigError => f.write_str("BluejayConfigError"),
         }
     }
@@ -89,10 +284,21 @@ impl fmt::Display for Error {
 
 
 /// Defines endianness type.
+///
+/// This is the data representation named by `head_rep` and `data_rep`, so it covers the floating
+/// point format as well as the byte order.  The VAX variants always imply little-endian integers
+/// and differ from `Little` only in how floats and doubles are stored, so use `is_little_endian`
+/// rather than matching on `Little` to find the byte order of integers.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Endianness {
     Big,
     Little,
+
+    /// VAX, with little-endian integers, VAX F floats (SF) and VAX D doubles (SD).
+    VaxD,
+
+    /// VAX, with little-endian integers, VAX F floats (SF) and VAX G doubles (SD).
+    VaxG,
 }
 
 impl fmt::Display for Endianness {
@@ -100,10 +306,29 @@ impl fmt::Display for Endianness {
         match self {
             Endianness::Big => write!(f, "big"),
             Endianness::Little => write!(f, "little"),
+            Endianness::VaxD => write!(f, "vaxd"),
+            Endianness::VaxG => write!(f, "vaxg"),
         }
     }
 }
 
+impl Endianness {
+    /// The four byte representation used in the header (EEEI, IEEE, VAXD or VAXG).
+    pub fn as_bytes(&self) -> &'static [u8; 4] {
+        match self {
+            Endianness::Big => b"IEEE",
+            Endianness::Little => b"EEEI",
+            Endianness::VaxD => b"VAXD",
+            Endianness::VaxG => b"VAXG",
+        }
+    }
+
+    /// Whether integers are stored least significant byte first.
+    pub fn is_little_endian(&self) -> bool {
+        !matches!(self, Endianness::Big)
+    }
+}
+
 /// Converts raw bytes to an Endianness enum type.
 impl TryFrom<&[u8]> for Endianness {
     type Error = Error;
@@ -113,6 +338,10 @@ impl TryFrom<&[u8]> for Endianness {
             Ok(Endianness::Little)
         } else if v[0] == b'I' && v[1] == b'E' && v[2] == b'E' && v[3] == b'E' {
             Ok(Endianness::Big)
+        } else if &v[0..4] == b"VAXD" {
+            Ok(Endianness::VaxD)
+        } else if &v[0..4] == b"VAXG" {
+            Ok(Endianness::VaxG)
         } else {
             Err(Error::InvalidEndianness)
         }
@@ -139,30 +368,74 @@ impl fmt::Display for DataType {
 }
 
 impl DataType {
-    pub fn num_bytes(&self) -> Result<usize> {
-        let mult = match self.rank {
-            b'S' => 1,
-            b'C' => 2,
-            _ => return Err(Error::UnknownRankError),
-        };
+    /// Number of components in each element: 1 for scalars, 2 for complex, 3 for vectors, 4 for
+    /// quaternions, 9 and 16 for 3x3 and 4x4 matrices, and 1-9 for multi-channel data.
+    pub fn num_elements(&self) -> Result<usize> {
+        match self.rank {
+            b'S' => Ok(1),
+            b'C' => Ok(2),
+            b'V' => Ok(3),
+            b'Q' => Ok(4),
+            b'M' => Ok(9),
+            b'T' => Ok(16),
+            b'1'..=b'9' => Ok((self.rank - b'0') as usize),
+            _ => Err(Error::UnknownRankError),
+        }
+    }
 
-        let size = match self.format {
-            b'B' => 1,
-            b'I' => 2,
-            b'L' => 4,
-            b'X' => 8,
-            b'F' => 4,
-            b'D' => 8,
-            _ => return Err(Error::UnknownFormatError),
-        };
+    /// Size of a single component in bits.
+    pub fn element_bits(&self) -> Result<usize> {
+        match self.format {
+            b'P' => Ok(1),
+            b'N' => Ok(4),
+            _ => Ok(self.element_size()? * 8),
+        }
+    }
+
+    /// Size of a whole element in bits.  Unlike `num_bytes`, this works for the packed bit (P) and
+    /// nibble (N) formats.
+    pub fn num_bits(&self) -> Result<usize> {
+        Ok(self.num_elements()? * self.element_bits()?)
+    }
 
-        Ok(mult * size)
+    /// Whether the format packs several components into each byte.
+    pub fn is_packed(&self) -> bool {
+        matches!(self.format, b'P' | b'N')
+    }
+
+    /// Size of a single component in bytes.  Packed formats have no byte size, so they produce a
+    /// `PackedFormatError`.
+    pub fn element_size(&self) -> Result<usize> {
+        match self.format {
+            b'A' => Ok(1),
+            b'B' => Ok(1),
+            b'O' => Ok(1),
+            b'I' => Ok(2),
+            b'U' => Ok(2),
+            b'L' => Ok(4),
+            b'V' => Ok(4),
+            b'X' => Ok(8),
+            b'F' => Ok(4),
+            b'D' => Ok(8),
+            b'P' | b'N' => Err(Error::PackedFormatError),
+            _ => Err(Error::UnknownFormatError),
+        }
+    }
+
+    /// Size of a whole element in bytes.
+    pub fn num_bytes(&self) -> Result<usize> {
+        Ok(self.num_elements()? * self.element_size()?)
+    }
+
+    /// The data type of a single component, such as SF for VF.
+    pub fn scalar(&self) -> DataType {
+        DataType{rank: b'S', format: self.format}
     }
 }
 
 /// Reads the extended header keywords.
-pub fn read_ext_header(mut file: &File, header: &Header) -> Result<Vec<ExtKeyword>> {
-    match file.seek(SeekFrom::Start(header.ext_start as u64)) {
+pub fn read_ext_header<R: Read + Seek>(mut reader: R, header: &Header) -> Result<Vec<ExtKeyword>> {
+    match reader.seek(SeekFrom::Start(header.ext_start as u64)) {
         Ok(x) => x,
         Err(_) => return Err(Error::ExtHeaderSeekError),
     };
@@ -172,19 +445,29 @@ pub fn read_ext_header(mut file: &File, header: &Header) -> Result<Vec<ExtKeywor
 
     while consumed < header.ext_size {
         let mut key_length_buf = vec![0_u8; EXT_KEYWORD_LENGTH];
-        consumed += match file.read_exact(&mut key_length_buf) {
+        consumed += match reader.read_exact(&mut key_length_buf) {
             Ok(_) => EXT_KEYWORD_LENGTH,
             Err(_) => break,
         };
 
         // entire length of keyword block: tag, data, kwhdr & padding
-        let key_length = bytes_to_i32(&key_length_buf, header.header_endianness).unwrap() as usize;
+        let key_length = bytes_to_i32(&key_length_buf, header.header_endianness)?;
+        if key_length < EXT_KEYWORD_HEADER_LENGTH as i32 {
+            return Err(Error::ExtHeaderKeywordLengthParseError);
+        }
+
+        // the block has to fit in what is left of the extended header, counting its length field
+        let key_length = key_length as usize;
+        if key_length > header.ext_size.saturating_sub(consumed) + EXT_KEYWORD_LENGTH {
+            return Err(Error::ExtHeaderKeywordLengthParseError);
+        }
+
         let mut key_buf = vec![0_u8; key_length-EXT_KEYWORD_LENGTH];
-        consumed += match file.read_exact(&mut key_buf) {
+        consumed += match reader.read_exact(&mut key_buf) {
             Ok(_) => key_length-EXT_KEYWORD_LENGTH,
             Err(_) => break,
         };
-        let keyword = parse_ext_keyword(&key_buf, key_length, header.header_endianness).unwrap();
+        let keyword = parse_ext_keyword(&key_buf, key_length, header.header_endianness)?;
         keywords.push(keyword);
     }
 
@@ -193,46 +476,228 @@ pub fn read_ext_header(mut file: &File, header: &Header) -> Result<Vec<ExtKeywor
 
 /// Represents an extended header keyword value with the necessary information to render it from
 /// raw bytes.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ExtKeywordValue {
     pub format: char,
     pub endianness: Endianness,
     pub raw_value: Vec<u8>,
 }
 
+impl ExtKeywordValue {
+    /// Creates an ASCII (A) value.
+    pub fn from_string(v: &str, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'A', endianness, raw_value: v.as_bytes().to_vec()}
+    }
+
+    /// Creates a byte (B) value.
+    pub fn from_i8(v: i8, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'B', endianness, raw_value: v.to_ne_bytes().to_vec()}
+    }
+
+    /// Creates an integer (I) value.
+    pub fn from_i16(v: i16, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'I', endianness, raw_value: i16_to_bytes(v, endianness).to_vec()}
+    }
+
+    /// Creates a long (L) value.
+    pub fn from_i32(v: i32, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'L', endianness, raw_value: i32_to_bytes(v, endianness).to_vec()}
+    }
+
+    /// Creates an extra long (X) value.
+    pub fn from_i64(v: i64, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'X', endianness, raw_value: i64_to_bytes(v, endianness).to_vec()}
+    }
+
+    /// Creates a float (F) value.
+    pub fn from_f32(v: f32, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'F', endianness, raw_value: f32_to_bytes(v, endianness).to_vec()}
+    }
+
+    /// Creates a double (D) value.
+    pub fn from_f64(v: f64, endianness: Endianness) -> Self {
+        ExtKeywordValue{format: 'D', endianness, raw_value: f64_to_bytes(v, endianness).to_vec()}
+    }
+
+    fn is_string(&self) -> bool {
+        matches!(self.format, 'A' | 'S' | 'Z')
+    }
+
+    fn data_type(&self) -> Result<DataType> {
+        if !self.format.is_ascii() {
+            return Err(Error::UnknownFormatError);
+        }
+
+        Ok(DataType{rank: b'S', format: self.format as u8})
+    }
+
+    /// Number of elements in the value.  An ASCII value is a single element.
+    pub fn num_elements(&self) -> Result<usize> {
+        if self.is_string() {
+            return Ok(1);
+        }
+
+        let size = self.data_type()?.num_bytes()?;
+        if !self.raw_value.len().is_multiple_of(size) {
+            return Err(Error::ByteConversionError);
+        }
+
+        Ok(self.raw_value.len() / size)
+    }
+
+    /// Decodes every element of the value.  An ASCII value decodes to a single string.
+    pub fn values(&self) -> Result<Vec<Value>> {
+        if self.is_string() {
+            return Ok(vec![Value::String(self.as_str()?.to_string())]);
+        }
+
+        let data_type = self.data_type()?;
+        let size = data_type.num_bytes()?;
+
+        (0..self.num_elements()?)
+            .map(|i| Value::decode(&data_type, &self.raw_value[i*size..], self.endianness))
+            .collect()
+    }
+
+    /// Decodes the value.  A value with a single element decodes to that element, while one with
+    /// several elements decodes to an array.
+    pub fn value(&self) -> Result<Value> {
+        let mut values = self.values()?;
+
+        if values.len() == 1 {
+            return Ok(values.remove(0));
+        }
+
+        Ok(Value::Array(values))
+    }
+
+    /// Converts a single numeric value to an f64.  Fails for arrays, ASCII and unknown formats.
+    pub fn as_f64(&self) -> Result<f64> {
+        self.value()?.as_f64()
+    }
+
+    /// Converts a single integer value to an i64.  Fails for arrays, floating point, ASCII and
+    /// unknown formats.
+    pub fn as_i64(&self) -> Result<i64> {
+        self.value()?.as_i64()
+    }
+
+    /// Converts every element of a numeric value to an f64.
+    pub fn as_f64_vec(&self) -> Result<Vec<f64>> {
+        self.values()?.iter().map(|v| v.as_f64()).collect()
+    }
+
+    /// Converts every element of an integer value to an i64.
+    pub fn as_i64_vec(&self) -> Result<Vec<i64>> {
+        self.values()?.iter().map(|v| v.as_i64()).collect()
+    }
+
+    /// Borrows an ASCII value as a string.  Fails for numeric formats.
+    pub fn as_str(&self) -> Result<&str> {
+        if !self.is_string() {
+            return Err(Error::ValueConversionError);
+        }
+
+        match from_utf8(&self.raw_value) {
+            Ok(x) => Ok(x),
+            Err(_) => Err(Error::ValueConversionError),
+        }
+    }
+
+    /// The raw bytes of the value, in the keyword's endianness.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.raw_value
+    }
+}
+
 impl fmt::Display for ExtKeywordValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.format {
-            'A' | 'S' | 'Z' => write!(f, "\"{}\"", from_utf8(&self.raw_value).unwrap().replace('\"', "\\\"")),
-            'B' => write!(f, "{}", byte_to_i8(self.raw_value[0]).unwrap()),
-            'O' => write!(f, "{}", self.raw_value[0]),
-            'I' => write!(f, "{}", bytes_to_i16(&self.raw_value[0..2], self.endianness).unwrap()),
-            'L' => write!(f, "{}", bytes_to_i32(&self.raw_value[0..4], self.endianness).unwrap()),
-            'X' => write!(f, "{}", bytes_to_i64(&self.raw_value[0..8], self.endianness).unwrap()),
-            'F' => write!(f, "{}", bytes_to_f32(&self.raw_value[0..4], self.endianness).unwrap()),
-            'D' => write!(f, "{}", bytes_to_f64(&self.raw_value[0..8], self.endianness).unwrap()),
-            _ => write!(f, "\"?\""),
+        match self.value() {
+            Ok(v) => write!(f, "{}", v),
+            Err(_) => write!(f, "\"?\""),
         }
     }
 }
 
 /// Extended header keyword.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ExtKeyword {
     pub length: usize,
     pub tag: String,
     pub value: ExtKeywordValue,
 }
 
+impl ExtKeyword {
+    /// Creates a keyword, working out the length of its padded keyword block.
+    pub fn new(tag: &str, value: ExtKeywordValue) -> Self {
+        let length = keyword_block_length(value.raw_value.len(), tag.len());
+
+        ExtKeyword{
+            length,
+            tag: tag.to_string(),
+            value,
+        }
+    }
+}
+
+/// Length of a keyword block: the keyword header, value and tag, padded to a multiple of 8 bytes.
+pub(crate) fn keyword_block_length(value_length: usize, tag_length: usize) -> usize {
+    (EXT_KEYWORD_HEADER_LENGTH + value_length + tag_length).div_ceil(8) * 8
+}
+
+/// Parses a contiguous run of keyword blocks, as found in the extended header or the data of a type
+/// 4000 file.
+pub(crate) fn parse_keyword_blocks(v: &[u8], endianness: Endianness) -> Result<Vec<ExtKeyword>> {
+    let mut keywords: Vec<ExtKeyword> = vec![];
+    let mut consumed: usize = 0;
+
+    while consumed + EXT_KEYWORD_LENGTH <= v.len() {
+        let key_length = bytes_to_i32(&v[consumed..consumed+EXT_KEYWORD_LENGTH], endianness)?;
+
+        if key_length == 0 {
+            // zero padding after the last keyword
+            break;
+        }
+
+        if key_length < EXT_KEYWORD_HEADER_LENGTH as i32 || consumed + key_length as usize > v.len() {
+            return Err(Error::ExtHeaderKeywordLengthParseError);
+        }
+
+        let key_length = key_length as usize;
+        let keyword = parse_ext_keyword(&v[consumed+EXT_KEYWORD_LENGTH..consumed+key_length], key_length, endianness)?;
+        keywords.push(keyword);
+        consumed += key_length;
+    }
+
+    Ok(keywords)
+}
+
 fn parse_ext_keyword(v: &[u8], key_length: usize, endianness: Endianness) -> Result<ExtKeyword> {
+    if v.len() < EXT_KEYWORD_HEADER_LENGTH - EXT_KEYWORD_LENGTH {
+        return Err(Error::ExtHeaderKeywordLengthParseError);
+    }
+
     // Note that 4 is subtracted from the offsets because key_length was already read
     let extra_length = bytes_to_i16(&v[0..2], endianness)? as usize;  // length of the keyword header, tag & padding
     let tag_length = v[2] as usize;  // length of just the tag
     let format = v[3] as char;
 
+    if extra_length > key_length {
+        return Err(Error::ExtHeaderKeywordLengthParseError);
+    }
+
     let value_offset: usize = 4;
     let value_length: usize = key_length - extra_length;
     let tag_offset: usize = value_offset + value_length;
 
-    let tag = from_utf8(&v[tag_offset..tag_offset+tag_length]).unwrap().to_string();
+    if tag_offset + tag_length > v.len() {
+        return Err(Error::ExtHeaderKeywordLengthParseError);
+    }
+
+    let tag = match from_utf8(&v[tag_offset..tag_offset+tag_length]) {
+        Ok(x) => x.to_string(),
+        Err(_) => return Err(Error::ExtHeaderKeywordTagParseError),
+    };
     let raw_value = v[value_offset..value_offset+value_length].to_vec();
     let value = ExtKeywordValue{
         format,
@@ -255,7 +720,7 @@ pub struct HeaderKeyword {
 }
 
 /// Represents the main header.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Header {
     /// Endianness of the values in the header.
     pub header_endianness: Endianness,
@@ -263,6 +728,15 @@ pub struct Header {
     /// Endianness of the values in the data.
     pub data_endianness: Endianness,
 
+    /// Non-zero when the data is stored in a separate file from the header.
+    pub detached: i32,
+
+    /// Non-zero when the file is protected from being overwritten.
+    pub protected: i32,
+
+    /// Non-zero when the file is a pipe (a circular buffer) rather than a regular file.
+    pub pipe: i32,
+
     /// Extended header start location in bytes.
     pub ext_start: usize,
 
@@ -283,31 +757,88 @@ pub struct Header {
     /// The rank and format of the data (SD, CF, NH, etc.).
     pub data_type: DataType,
 
+    /// Bit flags describing the file.
+    pub flagmask: i16,
+
     /// The start time of the data in seconds since January 1, 1950.
     pub timecode: f64,
 
+    /// Index of the pipe inlet.
+    pub inlet: i16,
+
+    /// Number of pipe outlets.
+    pub outlets: i16,
+
+    /// Bit mask of the active pipe outlets.
+    pub outmask: i32,
+
+    /// Location of the pipe buffer.
+    pub pipeloc: i32,
+
+    /// Size of the pipe buffer in bytes.
+    pub pipesize: i32,
+
+    /// Total number of bytes written to the pipe.  Stored as a 64 bit float like `data_size`.
+    pub in_byte: f64,
+
+    /// Total number of bytes read from the pipe by the first outlet.
+    pub out_byte: f64,
+
+    /// Total number of bytes read from the pipe by each of the outlets.
+    pub outbytes: [f64; 8],
+
+    /// Length of the main header keywords in bytes, as stored in the file.
+    pub keylength: usize,
+
     /// Keywords from the main header (not extended header keywords).
     pub keywords: Vec<HeaderKeyword>,
 }
 
-/// Represents the adjunct header fields for type 1000 files.
-#[derive(Clone, Debug)]
-pub struct Type1000Adjunct {
-    pub xstart: f64,
-    pub xdelta: f64,
-    pub xunits: i32,
+/// An attached, little-endian type 1000 header for scalar doubles, with the data right after the
+/// adjunct header and every other field zero.
+impl Default for Header {
+    fn default() -> Self {
+        Header{
+            header_endianness: Endianness::Little,
+            data_endianness: Endianness::Little,
+            detached: 0,
+            protected: 0,
+            pipe: 0,
+            ext_start: 0,
+            ext_size: 0,
+            data_start: (COMMON_HEADER_SIZE + ADJUNCT_HEADER_SIZE) as f64,
+            data_size: 0.0,
+            type_code: 1000,
+            data_type: DataType{rank: b'S', format: b'D'},
+            flagmask: 0,
+            timecode: 0.0,
+            inlet: 0,
+            outlets: 0,
+            outmask: 0,
+            pipeloc: 0,
+            pipesize: 0,
+            in_byte: 0.0,
+            out_byte: 0.0,
+            outbytes: [0.0; 8],
+            keylength: 0,
+            keywords: vec![],
+        }
+    }
 }
 
-/// Represents the adjunct header fields for type 2000 files.
-#[derive(Clone, Debug)]
-pub struct Type2000Adjunct {
-    pub xstart: f64,
-    pub xdelta: f64,
-    pub xunits: i32,
-    pub subsize: i32,
-    pub ystart: f64,
-    pub ydelta: f64,
-    pub yunits: i32,
+impl Header {
+    /// Whether the data lives in a separate file from the header.
+    pub fn is_detached(&self) -> bool {
+        self.detached != 0
+    }
+
+    /// A copy of a detached header that describes the detached data file on its own.  Pass it to
+    /// the data readers along with the file from `open_data_file`.
+    pub fn attached(&self) -> Header {
+        let mut header = self.clone();
+        header.detached = 0;
+        header
+    }
 }
 
 fn is_blue(v: &[u8]) -> bool {
@@ -322,14 +853,29 @@ pub fn parse_header(data: &[u8]) -> Result<Header> {
 
     let header_endianness = Endianness::try_from(&data[4..8])?;
     let data_endianness = Endianness::try_from(&data[8..12])?;
+    let detached = bytes_to_i32(&data[12..16], header_endianness)?;
+    let protected = bytes_to_i32(&data[16..20], header_endianness)?;
+    let pipe = bytes_to_i32(&data[20..24], header_endianness)?;
     let ext_start = (bytes_to_i32(&data[24..28], header_endianness)? as usize) * 512;
     let ext_size = bytes_to_i32(&data[28..32], header_endianness)? as usize;
     let data_start = bytes_to_f64(&data[32..40], header_endianness)?;
     let data_size = bytes_to_f64(&data[40..48], header_endianness)?;
     let type_code = parse_type_code(&data[48..52], header_endianness)?;
     let data_type = DataType{rank: data[52], format: data[53]};
+    let flagmask = bytes_to_i16(&data[54..56], header_endianness)?;
     let timecode = bytes_to_f64(&data[56..64], header_endianness)?;
-    let keylength: usize = match bytes_to_i32(&data[160..164], header_endianness).unwrap().try_into() {
+    let inlet = bytes_to_i16(&data[64..66], header_endianness)?;
+    let outlets = bytes_to_i16(&data[66..68], header_endianness)?;
+    let outmask = bytes_to_i32(&data[68..72], header_endianness)?;
+    let pipeloc = bytes_to_i32(&data[72..76], header_endianness)?;
+    let pipesize = bytes_to_i32(&data[76..80], header_endianness)?;
+    let in_byte = bytes_to_f64(&data[80..88], header_endianness)?;
+    let out_byte = bytes_to_f64(&data[88..96], header_endianness)?;
+    let mut outbytes = [0.0; 8];
+    for (i, x) in outbytes.iter_mut().enumerate() {
+        *x = bytes_to_f64(&data[96+i*8..104+i*8], header_endianness)?;
+    }
+    let keylength: usize = match bytes_to_i32(&data[160..164], header_endianness)?.try_into() {
         Ok(x) => x,
         Err(_) => return Err(Error::HeaderKeywordLengthParseError),
     };
@@ -339,28 +885,41 @@ pub fn parse_header(data: &[u8]) -> Result<Header> {
     let header = Header{
         header_endianness,
         data_endianness,
+        detached,
+        protected,
+        pipe,
         ext_start,
         ext_size,
         data_start,
         data_size,
         type_code,
         data_type,
+        flagmask,
         timecode,
+        inlet,
+        outlets,
+        outmask,
+        pipeloc,
+        pipesize,
+        in_byte,
+        out_byte,
+        outbytes,
+        keylength,
         keywords,
     };
 
     Ok(header)
 }
 
-/// Reads the main header from a file.
-pub fn read_header(mut file: &File) -> Result<Header> {
-    match file.seek(SeekFrom::Start(COMMON_HEADER_OFFSET as u64)) {
+/// Reads the main header from a file or any other seekable source.
+pub fn read_header<R: Read + Seek>(mut reader: R) -> Result<Header> {
+    match reader.seek(SeekFrom::Start(COMMON_HEADER_OFFSET as u64)) {
         Ok(x) => x,
         Err(_) => return Err(Error::HeaderSeekError),
     };
 
     let mut header_data = vec![0_u8; COMMON_HEADER_SIZE];
-    let n = match file.read(&mut header_data) {
+    let n = match reader.read(&mut header_data) {
         Ok(x) => x,
         Err(_) => return Err(Error::FileReadError),
     };
@@ -425,78 +984,9 @@ fn parse_type_code(v: &[u8], endianness: Endianness) -> Result<TypeCode> {
     }
 }
 
-/// Reads the adjunct header from a type 1000 file.
-pub fn read_type1000_adjunct_header(mut file: &File, header: &Header) -> Result<Type1000Adjunct> {
-    match file.seek(SeekFrom::Start(ADJUNCT_HEADER_OFFSET as u64)) {
-        Ok(x) => x,
-        Err(_) => return Err(Error::AdjunctHeaderSeekError),
-    };
-
-    let mut data = vec![0_u8; ADJUNCT_HEADER_SIZE];
-    let n = match file.read(&mut data) {
-        Ok(x) => x,
-        Err(_) => return Err(Error::FileReadError),
-    };
-
-    if n < ADJUNCT_HEADER_SIZE {
-        return Err(Error::NotEnoughAdjunctHeaderBytes(n))
-    }
-
-    let endianness = header.header_endianness;
-    let xstart: f64 = bytes_to_f64(&data[0..8], endianness)?;
-    let xdelta: f64 = bytes_to_f64(&data[8..16], endianness)?;
-    let xunits: i32 = bytes_to_i32(&data[16..20], endianness)?;
-
-    Ok(Type1000Adjunct{
-        xstart,
-        xdelta,
-        xunits,
-    })
-}
-
-/// Reads the adjunct header from a type 2000 file.
-pub fn read_type2000_adjunct_header(mut file: &File, header: &Header) -> Result<Type2000Adjunct> {
-    match file.seek(SeekFrom::Start(ADJUNCT_HEADER_OFFSET as u64)) {
-        Ok(x) => x,
-        Err(_) => return Err(Error::AdjunctHeaderSeekError),
-    };
-
-    let mut data = vec![0_u8; ADJUNCT_HEADER_SIZE];
-    let n = match file.read(&mut data) {
-        Ok(x) => x,
-        Err(_) => return Err(Error::FileReadError),
-    };
-
-    if n < ADJUNCT_HEADER_SIZE {
-        return Err(Error::NotEnoughAdjunctHeaderBytes(n))
-    }
-
-    let endianness = header.header_endianness;
-    let xstart: f64 = bytes_to_f64(&data[0..8], endianness)?;
-    let xdelta: f64 = bytes_to_f64(&data[8..16], endianness)?;
-    let xunits: i32 = bytes_to_i32(&data[16..20], endianness)?;
-    let subsize: i32 = bytes_to_i32(&data[20..24], endianness)?;
-    let ystart: f64 = bytes_to_f64(&data[24..32], endianness)?;
-    let ydelta: f64 = bytes_to_f64(&data[32..40], endianness)?;
-    let yunits: i32 = bytes_to_i32(&data[40..44], endianness)?;
-
-    Ok(Type2000Adjunct{
-        xstart,
-        xdelta,
-        xunits,
-        subsize,
-        ystart,
-        ydelta,
-        yunits,
-    })
-}
-
 /// Converts a byte to an i8.
 pub fn byte_to_i8(v: u8) -> Result<i8> {
-    match i8::try_from(v) {
-        Ok(x) => Ok(x),
-        Err(_) => Err(Error::ByteConversionError),
-    }
+    Ok(i8::from_ne_bytes([v]))
 }
 
 /// Converts bytes to an i16.
@@ -506,13 +996,27 @@ pub fn bytes_to_i16(v: &[u8], endianness: Endianness) -> Result<i16> {
         Err(_) => return Err(Error::ByteConversionError),
     };
 
-    if endianness == Endianness::Little {
+    if endianness.is_little_endian() {
         Ok(i16::from_le_bytes(b))
     } else {
         Ok(i16::from_be_bytes(b))
     }
 }
 
+/// Converts bytes to a u16.
+pub fn bytes_to_u16(v: &[u8], endianness: Endianness) -> Result<u16> {
+    let b: [u8; 2] = match v.try_into() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::ByteConversionError),
+    };
+
+    if endianness.is_little_endian() {
+        Ok(u16::from_le_bytes(b))
+    } else {
+        Ok(u16::from_be_bytes(b))
+    }
+}
+
 /// Concerts bytes to an i32.
 pub fn bytes_to_i32(v: &[u8], endianness: Endianness) -> Result<i32> {
     let b: [u8; 4] = match v.try_into() {
@@ -520,13 +1024,27 @@ pub fn bytes_to_i32(v: &[u8], endianness: Endianness) -> Result<i32> {
         Err(_) => return Err(Error::ByteConversionError),
     };
 
-    if endianness == Endianness::Little {
+    if endianness.is_little_endian() {
         Ok(i32::from_le_bytes(b))
     } else {
         Ok(i32::from_be_bytes(b))
     }
 }
 
+/// Converts bytes to a u32.
+pub fn bytes_to_u32(v: &[u8], endianness: Endianness) -> Result<u32> {
+    let b: [u8; 4] = match v.try_into() {
+        Ok(x) => x,
+        Err(_) => return Err(Error::ByteConversionError),
+    };
+
+    if endianness.is_little_endian() {
+        Ok(u32::from_le_bytes(b))
+    } else {
+        Ok(u32::from_be_bytes(b))
+    }
+}
+
 /// Converts bytes to an i64.
 pub fn bytes_to_i64(v: &[u8], endianness: Endianness) -> Result<i64> {
     let b: [u8; 8] = match v.try_into() {
@@ -534,7 +1052,7 @@ pub fn bytes_to_i64(v: &[u8], endianness: Endianness) -> Result<i64> {
         Err(_) => return Err(Error::ByteConversionError),
     };
 
-    if endianness == Endianness::Little {
+    if endianness.is_little_endian() {
         Ok(i64::from_le_bytes(b))
     } else {
         Ok(i64::from_be_bytes(b))
@@ -548,10 +1066,10 @@ pub fn bytes_to_f32(v: &[u8], endianness: Endianness) -> Result<f32> {
         Err(_) => return Err(Error::ByteConversionError),
     };
 
-    if endianness == Endianness::Little {
-        Ok(f32::from_le_bytes(b))
-    } else {
-        Ok(f32::from_be_bytes(b))
+    match endianness {
+        Endianness::Little => Ok(f32::from_le_bytes(b)),
+        Endianness::Big => Ok(f32::from_be_bytes(b)),
+        Endianness::VaxD | Endianness::VaxG => Ok(vax_f_to_f32(&b)),
     }
 }
 
@@ -562,10 +1080,57 @@ pub fn bytes_to_f64(v: &[u8], endianness: Endianness) -> Result<f64> {
         Err(_) => return Err(Error::ByteConversionError),
     };
 
-    if endianness == Endianness::Little {
-        Ok(f64::from_le_bytes(b))
+    match endianness {
+        Endianness::Little => Ok(f64::from_le_bytes(b)),
+        Endianness::Big => Ok(f64::from_be_bytes(b)),
+        Endianness::VaxD => Ok(vax_d_to_f64(&b)),
+        Endianness::VaxG => Ok(vax_g_to_f64(&b)),
+    }
+}
+
+/// Converts an i16 to bytes.
+pub fn i16_to_bytes(v: i16, endianness: Endianness) -> [u8; 2] {
+    if endianness.is_little_endian() {
+        v.to_le_bytes()
+    } else {
+        v.to_be_bytes()
+    }
+}
+
+/// Converts an i32 to bytes.
+pub fn i32_to_bytes(v: i32, endianness: Endianness) -> [u8; 4] {
+    if endianness.is_little_endian() {
+        v.to_le_bytes()
+    } else {
+        v.to_be_bytes()
+    }
+}
+
+/// Converts an i64 to bytes.
+pub fn i64_to_bytes(v: i64, endianness: Endianness) -> [u8; 8] {
+    if endianness.is_little_endian() {
+        v.to_le_bytes()
     } else {
-        Ok(f64::from_be_bytes(b))
+        v.to_be_bytes()
+    }
+}
+
+/// Converts an f32 to bytes.
+pub fn f32_to_bytes(v: f32, endianness: Endianness) -> [u8; 4] {
+    match endianness {
+        Endianness::Little => v.to_le_bytes(),
+        Endianness::Big => v.to_be_bytes(),
+        Endianness::VaxD | Endianness::VaxG => f32_to_vax_f(v),
+    }
+}
+
+/// Converts an f64 to bytes.
+pub fn f64_to_bytes(v: f64, endianness: Endianness) -> [u8; 8] {
+    match endianness {
+        Endianness::Little => v.to_le_bytes(),
+        Endianness::Big => v.to_be_bytes(),
+        Endianness::VaxD => f64_to_vax_d(v),
+        Endianness::VaxG => f64_to_vax_g(v),
     }
 }
 
@@ -576,6 +1141,11 @@ pub fn bytes_to_complex_i8(v: &[u8]) -> Result<Complex<i8>> {
     Ok(Complex::<i8>::new(real, imag))
 }
 
+/// Converts bytes to a complex u8 (CO).
+pub fn bytes_to_complex_u8(v: &[u8]) -> Result<Complex<u8>> {
+    Ok(Complex::<u8>::new(v[0], v[1]))
+}
+
 /// Converts bytes to a complex i16 (CI).
 pub fn bytes_to_complex_i16(v: &[u8], endianness: Endianness) -> Result<Complex<i16>> {
     let real: i16 = bytes_to_i16(&v[0..2], endianness)?;
@@ -583,6 +1153,13 @@ pub fn bytes_to_complex_i16(v: &[u8], endianness: Endianness) -> Result<Complex<
     Ok(Complex::<i16>::new(real, imag))
 }
 
+/// Converts bytes to a complex u16 (CU).
+pub fn bytes_to_complex_u16(v: &[u8], endianness: Endianness) -> Result<Complex<u16>> {
+    let real: u16 = bytes_to_u16(&v[0..2], endianness)?;
+    let imag: u16 = bytes_to_u16(&v[2..4], endianness)?;
+    Ok(Complex::<u16>::new(real, imag))
+}
+
 /// Converts bytes to a complex i32 (CL).
 pub fn bytes_to_complex_i32(v: &[u8], endianness: Endianness) -> Result<Complex<i32>> {
     let real: i32 = bytes_to_i32(&v[0..4], endianness)?;
@@ -590,6 +1167,13 @@ pub fn bytes_to_complex_i32(v: &[u8], endianness: Endianness) -> Result<Complex<
     Ok(Complex::<i32>::new(real, imag))
 }
 
+/// Converts bytes to a complex u32 (CV).
+pub fn bytes_to_complex_u32(v: &[u8], endianness: Endianness) -> Result<Complex<u32>> {
+    let real: u32 = bytes_to_u32(&v[0..4], endianness)?;
+    let imag: u32 = bytes_to_u32(&v[4..8], endianness)?;
+    Ok(Complex::<u32>::new(real, imag))
+}
+
 /// Converts bytes to a complex i64 (CX).
 pub fn bytes_to_complex_i64(v: &[u8], endianness: Endianness) -> Result<Complex<i64>> {
     let real: i64 = bytes_to_i64(&v[0..8], endianness)?;
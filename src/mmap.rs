@@ -0,0 +1,172 @@
+use std::borrow::Cow;
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+
+use bytemuck::Pod;
+use memmap2::Mmap;
+
+use crate::{
+    data::check_data_type,
+    detached_data_path,
+    Endianness,
+    Error,
+    Header,
+    parse_header,
+    Result,
+    Sample,
+    Type2000Adjunct,
+    COMMON_HEADER_SIZE,
+};
+
+/// A read-only, memory-mapped view of a bluefile.  Samples are handed out as slices directly into
+/// the mapping when the data endianness matches the host and the data is suitably aligned.
+/// Otherwise they are decoded into an owned copy.
+pub struct MappedBluefile {
+    mmap: Mmap,
+
+    /// Mapping of the data file when the header is detached.
+    data_mmap: Option<Mmap>,
+    header: Header,
+}
+
+fn open_file<P: AsRef<Path>>(path: P) -> Result<File> {
+    match File::open(path.as_ref()) {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::FileOpenError(path.as_ref().display().to_string())),
+    }
+}
+
+fn map(file: &File) -> Result<Mmap> {
+    // SAFETY: the mapping is read-only.  As with any memory map, the file must not be
+    // truncated by another process while it is mapped.
+    match unsafe { Mmap::map(file) } {
+        Ok(x) => Ok(x),
+        Err(_) => Err(Error::MemoryMapError),
+    }
+}
+
+fn is_native(endianness: Endianness) -> bool {
+    match endianness {
+        Endianness::Little => cfg!(target_endian = "little"),
+        Endianness::Big => cfg!(target_endian = "big"),
+        Endianness::VaxD | Endianness::VaxG => false,  // floats always need converting
+    }
+}
+
+impl MappedBluefile {
+    /// Opens and maps the bluefile at the given path.  When the header is detached, the companion
+    /// `.det` data file is mapped as well.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut bluefile = Self::from_file(&open_file(path.as_ref())?)?;
+
+        if bluefile.header.is_detached() {
+            bluefile.data_mmap = Some(map(&open_file(detached_data_path(path))?)?);
+        }
+
+        Ok(bluefile)
+    }
+
+    /// Opens and maps a detached header along with its data file at an explicit path.
+    pub fn open_detached<P: AsRef<Path>, Q: AsRef<Path>>(header_path: P, data_path: Q) -> Result<Self> {
+        let mut bluefile = Self::from_file(&open_file(header_path)?)?;
+        bluefile.data_mmap = Some(map(&open_file(data_path)?)?);
+        Ok(bluefile)
+    }
+
+    /// Maps an already opened bluefile.  A detached header has no data to map here, so use
+    /// `open_detached` for those.
+    pub fn from_file(file: &File) -> Result<Self> {
+        let mmap = map(file)?;
+
+        if mmap.len() < COMMON_HEADER_SIZE {
+            return Err(Error::NotEnoughHeaderBytes(mmap.len()));
+        }
+
+        let header = parse_header(&mmap[0..COMMON_HEADER_SIZE])?;
+
+        Ok(MappedBluefile{
+            mmap,
+            data_mmap: None,
+            header,
+        })
+    }
+
+    /// Main header of the mapped file.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Raw bytes of the data section.
+    pub fn data_bytes(&self) -> Result<&[u8]> {
+        let mmap = match &self.data_mmap {
+            Some(x) => x,
+            None if self.header.is_detached() => return Err(Error::DetachedDataError),
+            None => &self.mmap,
+        };
+
+        let start = self.header.data_start as usize;
+        let end = match start.checked_add(self.header.data_size as usize) {
+            Some(x) if x <= mmap.len() => x,
+            _ => return Err(Error::NotEnoughDataBytes(mmap.len().saturating_sub(start))),
+        };
+
+        Ok(&mmap[start..end])
+    }
+
+    /// Number of samples of type `T` in the data section.
+    pub fn num_samples<T: Sample>(&self) -> Result<usize> {
+        let sample_size = check_data_type::<T>(&self.header)?;
+        Ok((self.header.data_size as usize) / sample_size)
+    }
+
+    /// All of the samples in the data section.
+    pub fn samples<T: Sample + Pod>(&self) -> Result<Cow<'_, [T]>> {
+        let count = self.num_samples::<T>()?;
+        self.samples_range(0..count)
+    }
+
+    /// A range of samples from the data section, by sample index.
+    pub fn samples_range<T: Sample + Pod>(&self, range: Range<usize>) -> Result<Cow<'_, [T]>> {
+        let sample_size = check_data_type::<T>(&self.header)?;
+        let count = (self.header.data_size as usize) / sample_size;
+
+        if range.start > range.end || range.end > count {
+            return Err(Error::SampleOutOfRangeError(range.end));
+        }
+
+        let data = self.data_bytes()?;
+        let bytes = &data[range.start*sample_size..range.end*sample_size];
+
+        if is_native(self.header.data_endianness) {
+            if let Ok(v) = bytemuck::try_cast_slice::<u8, T>(bytes) {
+                return Ok(Cow::Borrowed(v));
+            }
+        }
+
+        let decoded: Result<Vec<T>> = bytes.chunks_exact(sample_size)
+            .map(|v| T::from_bytes(v, self.header.data_endianness))
+            .collect();
+        Ok(Cow::Owned(decoded?))
+    }
+
+    /// The frame at index `n` of a type 2000 file.
+    pub fn frame<T: Sample + Pod>(&self, adjunct: &Type2000Adjunct, n: usize) -> Result<Cow<'_, [T]>> {
+        if self.header.type_code / 1000 != 2 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        if adjunct.subsize <= 0 {
+            return Err(Error::InvalidSubsizeError(adjunct.subsize));
+        }
+
+        let subsize = adjunct.subsize as usize;
+        let num_frames = self.num_samples::<T>()? / subsize;
+
+        if n >= num_frames {
+            return Err(Error::FrameOutOfRangeError(n));
+        }
+
+        self.samples_range(n*subsize..(n+1)*subsize)
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+
+use crate::{
+    data::seek_data,
+    detached::check_attached,
+    Error,
+    Header,
+    Result,
+};
+
+/// Makes sure the header describes packed, attached data in a type 1000 or 2000 file.  Returns the
+/// number of bits in each component.
+fn check_packed_header(header: &Header) -> Result<usize> {
+    if !matches!(header.type_code / 1000, 1 | 2) {
+        return Err(Error::TypeCodeMismatchError);
+    }
+
+    check_attached(header)?;
+
+    if !header.data_type.is_packed() {
+        return Err(Error::DataTypeMismatchError);
+    }
+
+    header.data_type.element_bits()
+}
+
+/// Creates an iterator over the components of a packed bit (P) or nibble (N) file.  Each bit or
+/// nibble is yielded as its own byte, with the most significant bits of each byte coming first.
+/// Nibbles are yielded unsigned, from 0 to 15.
+pub fn iter_packed_data<R: Read + Seek>(mut reader: R, header: &Header) -> Result<PackedIter<R>> {
+    let bits = check_packed_header(header)?;
+    seek_data(&mut reader, header)?;
+
+    Ok(PackedIter{
+        reader: BufReader::new(reader),
+        bits,
+        byte: 0,
+        shift: 0,
+        remaining: (header.data_size as usize) * 8 / bits,
+    })
+}
+
+/// Reads all of the components of a packed bit (P) or nibble (N) file, expanding each bit or nibble
+/// to a byte.
+pub fn read_packed_data<R: Read + Seek>(reader: R, header: &Header) -> Result<Vec<u8>> {
+    iter_packed_data(reader, header)?.collect()
+}
+
+/// Iterates over the bits or nibbles of a packed file, reading bytes as needed.
+pub struct PackedIter<R> {
+    reader: BufReader<R>,
+    bits: usize,
+    byte: u8,
+    shift: usize,  // bits of the current byte that have not been yielded yet
+    remaining: usize,
+}
+
+impl<R: Read> Iterator for PackedIter<R> {
+    type Item = Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        if self.shift == 0 {
+            let mut buf = [0_u8; 1];
+            if self.reader.read_exact(&mut buf).is_err() {
+                self.remaining = 0;
+                return Some(Err(Error::FileReadError));
+            }
+
+            self.byte = buf[0];
+            self.shift = 8;
+        }
+
+        self.shift -= self.bits;
+        self.remaining -= 1;
+        let mask = (1_u16 << self.bits) as u8 - 1;
+        Some(Ok((self.byte >> self.shift) & mask))
+    }
+}
@@ -0,0 +1,226 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::{
+    data::check_data_type,
+    detached::check_attached,
+    Error,
+    Header,
+    read_header,
+    Result,
+    Sample,
+};
+
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Reads data out of the circular buffer of a pipe file as the producer writes it.
+///
+/// The buffer is `pipesize` bytes long and starts `pipeloc` bytes into the file, or at
+/// `data_start` when `pipeloc` is zero.  The producer counts every byte it has written in
+/// `in_byte`, so byte `n` of the stream lives at offset `n % pipesize` in the buffer.  Reading
+/// starts from `out_byte` and the header is re-read to pick up new data.  The reader never writes
+/// to the file.
+pub struct PipeReader<R> {
+    reader: R,
+    header: Header,
+    buffer_start: u64,
+    buffer_size: u64,
+    position: u64,
+    poll_interval: Duration,
+}
+
+impl<R: Read + Seek> PipeReader<R> {
+    /// Creates a reader for a pipe file, starting at its `out_byte`.
+    pub fn new(reader: R, header: &Header) -> Result<Self> {
+        check_attached(header)?;
+
+        if header.pipe == 0 {
+            return Err(Error::NotAPipeError);
+        }
+
+        if header.pipesize <= 0 {
+            return Err(Error::InvalidPipeSizeError(header.pipesize));
+        }
+
+        let buffer_start = if header.pipeloc > 0 { header.pipeloc as u64 } else { header.data_start as u64 };
+
+        Ok(PipeReader{
+            reader,
+            header: header.clone(),
+            buffer_start,
+            buffer_size: header.pipesize as u64,
+            position: header.out_byte as u64,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        })
+    }
+
+    /// Sets how long to sleep between checks for new data while waiting.
+    pub fn set_poll_interval(&mut self, poll_interval: Duration) {
+        self.poll_interval = poll_interval;
+    }
+
+    /// Header as of the last refresh.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Number of bytes of the stream read so far, counting from the start of the pipe.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Re-reads the header to pick up how far the producer has got.
+    pub fn refresh(&mut self) -> Result<()> {
+        self.header = read_header(&mut self.reader)?;
+        Ok(())
+    }
+
+    /// Number of bytes written but not yet read, as of the last refresh.
+    pub fn available(&self) -> u64 {
+        (self.header.in_byte as u64).saturating_sub(self.position)
+    }
+
+    /// Whether the producer has closed the pipe, as of the last refresh.
+    pub fn is_closed(&self) -> bool {
+        self.header.pipe == 0
+    }
+
+    fn read_buffer(&mut self, count: u64) -> Result<Vec<u8>> {
+        let lost = self.available().saturating_sub(self.buffer_size);
+        if lost > 0 {
+            // the producer has written over data that was never read
+            self.position += lost;
+            return Err(Error::PipeOverrunError(lost));
+        }
+
+        let mut data = vec![0_u8; count as usize];
+        let mut done = 0;
+
+        while done < data.len() {
+            let offset = (self.position + done as u64) % self.buffer_size;
+            let n = ((self.buffer_size - offset) as usize).min(data.len() - done);
+
+            if self.reader.seek(SeekFrom::Start(self.buffer_start + offset)).is_err() {
+                return Err(Error::DataSeekError);
+            }
+
+            if self.reader.read_exact(&mut data[done..done+n]).is_err() {
+                return Err(Error::FileReadError);
+            }
+
+            done += n;
+        }
+
+        self.position += count;
+        Ok(data)
+    }
+
+    /// Reads whatever has been written since the last read, without waiting.
+    pub fn read_available(&mut self) -> Result<Vec<u8>> {
+        self.refresh()?;
+        let count = self.available();
+        self.read_buffer(count)
+    }
+
+    /// Reads exactly `count` bytes, waiting for the producer to write them.  Gives up with
+    /// `PipeTimeoutError` after `timeout`, or waits forever if there is no timeout.  Produces
+    /// `PipeClosedError` if the pipe is closed before enough data arrives, and
+    /// `PipeReadTooLargeError` if `count` is more than the buffer can hold at once.
+    pub fn read_bytes(&mut self, count: usize, timeout: Option<Duration>) -> Result<Vec<u8>> {
+        if count as u64 > self.buffer_size {
+            return Err(Error::PipeReadTooLargeError(count));
+        }
+
+        let start = Instant::now();
+
+        loop {
+            self.refresh()?;
+
+            if self.available() >= count as u64 {
+                return self.read_buffer(count as u64);
+            }
+
+            if self.is_closed() {
+                return Err(Error::PipeClosedError);
+            }
+
+            if let Some(t) = timeout {
+                if start.elapsed() >= t {
+                    return Err(Error::PipeTimeoutError);
+                }
+            }
+
+            sleep(self.poll_interval);
+        }
+    }
+
+    /// Reads exactly `count` samples, waiting for the producer to write them.
+    pub fn read_samples<T: Sample>(&mut self, count: usize, timeout: Option<Duration>) -> Result<Vec<T>> {
+        let sample_size = check_data_type::<T>(&self.header)?;
+        let bytes = match count.checked_mul(sample_size) {
+            Some(x) => x,
+            None => return Err(Error::PipeReadTooLargeError(usize::MAX)),
+        };
+        let data = self.read_bytes(bytes, timeout)?;
+
+        data.chunks_exact(sample_size)
+            .map(|v| T::from_bytes(v, self.header.data_endianness))
+            .collect()
+    }
+
+    /// Reads the whole samples written since the last read, without waiting.  A partly written
+    /// sample is left for the next read.
+    fn read_whole_samples<T: Sample>(&mut self) -> Result<Vec<T>> {
+        let sample_size = check_data_type::<T>(&self.header)?;
+        self.refresh()?;
+
+        let count = self.available() - self.available() % sample_size as u64;
+        let data = self.read_buffer(count)?;
+
+        data.chunks_exact(sample_size)
+            .map(|v| T::from_bytes(v, self.header.data_endianness))
+            .collect()
+    }
+
+    /// Creates an iterator over the samples in the pipe, which waits for each one to be written and
+    /// ends when the pipe is closed.  Everything written so far is read at once and handed out
+    /// before the header is checked again.
+    pub fn samples<T: Sample>(&mut self) -> PipeSamples<'_, R, T> {
+        PipeSamples{
+            reader: self,
+            pending: vec![],
+        }
+    }
+}
+
+/// Iterates over the samples in a pipe as they are written.
+pub struct PipeSamples<'a, R, T> {
+    reader: &'a mut PipeReader<R>,
+    pending: Vec<T>,  // samples read but not yet handed out, in reverse order
+}
+
+impl<R: Read + Seek, T: Sample> Iterator for PipeSamples<'_, R, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(x) = self.pending.pop() {
+                return Some(Ok(x));
+            }
+
+            match self.reader.read_whole_samples::<T>() {
+                Ok(mut x) if !x.is_empty() => {
+                    x.reverse();
+                    self.pending = x;
+                },
+                Ok(_) if self.reader.is_closed() => return None,
+                Ok(_) => sleep(self.reader.poll_interval),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::ops::Range;
+
+use crate::{
+    adjunct::parse_name,
+    bytes_to_f64,
+    bytes_to_i32,
+    DataType,
+    detached::check_attached,
+    Endianness,
+    Error,
+    Header,
+    Result,
+    Type3000Adjunct,
+    Type5000Adjunct,
+    Type6000Adjunct,
+    Value,
+};
+
+const TYPE6000_SUBRECORD_SIZE: usize = 72;  // in bytes
+
+/// Describes one named field (subrecord) of the records in a record file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Subrecord {
+    pub name: String,
+    pub data_type: DataType,
+
+    /// Offset of the field from the start of the record in bytes.
+    pub offset: usize,
+
+    /// Number of elements in the field.  For ASCII fields this is the length of the string.
+    pub num_elts: usize,
+}
+
+impl Subrecord {
+    /// Size of the field in bytes.
+    pub fn num_bytes(&self) -> Result<usize> {
+        Ok(self.data_type.num_bytes()? * self.num_elts)
+    }
+
+    fn decode(&self, record: &[u8], endianness: Endianness) -> Result<Value> {
+        let end = self.offset + self.num_bytes()?;
+
+        if end > record.len() {
+            return Err(Error::InvalidSubrecordError(self.name.clone()));
+        }
+
+        let v = &record[self.offset..end];
+
+        if self.data_type.format == b'A' {
+            return Ok(Value::String(parse_name(v)?));
+        }
+
+        if self.num_elts == 1 {
+            return Value::decode(&self.data_type, v, endianness);
+        }
+
+        let values: Result<Vec<Value>> = v.chunks_exact(self.data_type.num_bytes()?)
+            .map(|x| Value::decode(&self.data_type, x, endianness))
+            .collect();
+        Ok(Value::Array(values?))
+    }
+}
+
+/// Describes one subrecord of a type 6000 file, as stored in the extended header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Type6000Subrecord {
+    pub name: String,
+    pub minval: f64,
+    pub maxval: f64,
+
+    /// Offset of the field from the start of the record in bytes.
+    pub offset: i32,
+    pub num_elts: i32,
+    pub units: i32,
+    pub data_type: DataType,
+
+    /// Prefix applied to the units, such as "K" or "M".
+    pub uprefix: String,
+}
+
+impl Type6000Subrecord {
+    /// The record layout part of the description.
+    pub fn subrecord(&self) -> Result<Subrecord> {
+        if self.offset < 0 || self.num_elts < 1 {
+            return Err(Error::InvalidSubrecordError(self.name.clone()));
+        }
+
+        Ok(Subrecord{
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            offset: self.offset as usize,
+            num_elts: self.num_elts as usize,
+        })
+    }
+}
+
+fn parse_type6000_subrecord(v: &[u8], endianness: Endianness) -> Result<Type6000Subrecord> {
+    Ok(Type6000Subrecord{
+        name: parse_name(&v[0..24])?,
+        minval: bytes_to_f64(&v[24..32], endianness)?,
+        maxval: bytes_to_f64(&v[32..40], endianness)?,
+        offset: bytes_to_i32(&v[40..44], endianness)?,
+        num_elts: bytes_to_i32(&v[44..48], endianness)?,
+        units: bytes_to_i32(&v[48..52], endianness)?,
+        data_type: DataType{rank: v[52], format: v[53]},
+        uprefix: parse_name(&v[54..56])?,
+    })
+}
+
+/// Reads the subrecord descriptions of a type 6000 file, which start `subr_offset` bytes into the
+/// extended header.
+pub fn read_type6000_subrecords<R: Read + Seek>(mut reader: R, header: &Header, adjunct: &Type6000Adjunct) -> Result<Vec<Type6000Subrecord>> {
+    if adjunct.subrecords < 0 {
+        return Err(Error::InvalidSubrecordCount(adjunct.subrecords));
+    }
+
+    if adjunct.subr_offset < 0 {
+        return Err(Error::ExtHeaderSeekError);
+    }
+
+    let offset = header.ext_start + adjunct.subr_offset as usize;
+    if reader.seek(SeekFrom::Start(offset as u64)).is_err() {
+        return Err(Error::ExtHeaderSeekError);
+    }
+
+    // the descriptions have to fit in the extended header, which also guards against a corrupt
+    // count asking for an enormous buffer
+    let available = header.ext_size.saturating_sub(adjunct.subr_offset as usize);
+    let size = match (adjunct.subrecords as usize).checked_mul(TYPE6000_SUBRECORD_SIZE) {
+        Some(x) if x <= available => x,
+        _ => return Err(Error::InvalidSubrecordCount(adjunct.subrecords)),
+    };
+
+    let mut data = vec![0_u8; size];
+    if reader.read_exact(&mut data).is_err() {
+        return Err(Error::ExtHeaderKeywordReadError);
+    }
+
+    data.chunks_exact(TYPE6000_SUBRECORD_SIZE)
+        .map(|v| parse_type6000_subrecord(v, header.header_endianness))
+        .collect()
+}
+
+/// A named, decoded field of a record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub value: Value,
+}
+
+/// A single record from a record file, with one field per subrecord.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub fields: Vec<Field>,
+}
+
+impl Record {
+    /// Looks up the value of a field by name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.fields.iter().find(|f| f.name == name).map(|f| &f.value)
+    }
+}
+
+/// Reads records made up of named subrecords, either sequentially or by record index.
+pub struct RecordReader<R> {
+    reader: R,
+    data_start: u64,
+    endianness: Endianness,
+    record_length: usize,
+    num_records: usize,
+    subrecords: Vec<Subrecord>,
+}
+
+impl<R: Read + Seek> RecordReader<R> {
+    /// Creates a record reader for a type 3000 file.
+    pub fn from_type3000(reader: R, header: &Header, adjunct: &Type3000Adjunct) -> Result<Self> {
+        if header.type_code / 1000 != 3 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        RecordReader::new(reader, header, adjunct.subrecord_table.clone(), adjunct.record_length)
+    }
+
+    /// Creates a record reader for a type 5000 file, with one field per component.
+    pub fn from_type5000(reader: R, header: &Header, adjunct: &Type5000Adjunct) -> Result<Self> {
+        if header.type_code / 1000 != 5 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        RecordReader::new(reader, header, adjunct.subrecord_table()?, adjunct.record_length)
+    }
+
+    /// Creates a record reader for a type 6000 file, reading the subrecord descriptions from the
+    /// extended header.
+    pub fn from_type6000(mut reader: R, header: &Header, adjunct: &Type6000Adjunct) -> Result<Self> {
+        if header.type_code / 1000 != 6 {
+            return Err(Error::TypeCodeMismatchError);
+        }
+
+        let subrecords: Result<Vec<Subrecord>> = read_type6000_subrecords(&mut reader, header, adjunct)?
+            .iter()
+            .map(|s| s.subrecord())
+            .collect();
+
+        RecordReader::new(reader, header, subrecords?, adjunct.record_length)
+    }
+
+    pub(crate) fn new(reader: R, header: &Header, subrecords: Vec<Subrecord>, record_length: i32) -> Result<Self> {
+        check_attached(header)?;
+
+        if record_length <= 0 {
+            return Err(Error::InvalidRecordLengthError(record_length));
+        }
+
+        let record_length = record_length as usize;
+
+        for subrecord in &subrecords {
+            if subrecord.offset + subrecord.num_bytes()? > record_length {
+                return Err(Error::InvalidSubrecordError(subrecord.name.clone()));
+            }
+        }
+
+        Ok(RecordReader{
+            reader,
+            data_start: header.data_start as u64,
+            endianness: header.data_endianness,
+            record_length,
+            num_records: (header.data_size as usize) / record_length,
+            subrecords,
+        })
+    }
+
+    /// Descriptions of the fields in each record.
+    pub fn subrecords(&self) -> &[Subrecord] {
+        &self.subrecords
+    }
+
+    /// Number of complete records in the file.
+    pub fn num_records(&self) -> usize {
+        self.num_records
+    }
+
+    fn parse_record(&self, data: &[u8]) -> Result<Record> {
+        let mut fields = Vec::with_capacity(self.subrecords.len());
+
+        for subrecord in &self.subrecords {
+            fields.push(Field{
+                name: subrecord.name.clone(),
+                value: subrecord.decode(data, self.endianness)?,
+            });
+        }
+
+        Ok(Record{fields})
+    }
+
+    fn read_bytes(&mut self, start: usize, count: usize) -> Result<Vec<u8>> {
+        let offset = self.data_start + (start * self.record_length) as u64;
+
+        if self.reader.seek(SeekFrom::Start(offset)).is_err() {
+            return Err(Error::DataSeekError);
+        }
+
+        let mut data = vec![0_u8; count * self.record_length];
+        if self.reader.read_exact(&mut data).is_err() {
+            return Err(Error::FileReadError);
+        }
+
+        Ok(data)
+    }
+
+    /// Reads the record at index `n`.
+    pub fn read_record(&mut self, n: usize) -> Result<Record> {
+        if n >= self.num_records {
+            return Err(Error::RecordOutOfRangeError(n));
+        }
+
+        let data = self.read_bytes(n, 1)?;
+        self.parse_record(&data)
+    }
+
+    /// Reads a contiguous range of records.
+    pub fn read_records(&mut self, range: Range<usize>) -> Result<Vec<Record>> {
+        if range.is_empty() {
+            return Ok(vec![]);
+        }
+
+        if range.end > self.num_records {
+            return Err(Error::RecordOutOfRangeError(range.end - 1));
+        }
+
+        let data = self.read_bytes(range.start, range.len())?;
+        data.chunks_exact(self.record_length)
+            .map(|v| self.parse_record(v))
+            .collect()
+    }
+
+    /// Creates an iterator over all of the records in the file.
+    pub fn records(&mut self) -> Records<'_, R> {
+        Records{
+            reader: self,
+            next_record: 0,
+        }
+    }
+}
+
+/// Iterates over the records of a record file in order.
+pub struct Records<'a, R> {
+    reader: &'a mut RecordReader<R>,
+    next_record: usize,
+}
+
+impl<R: Read + Seek> Iterator for Records<'_, R> {
+    type Item = Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_record >= self.reader.num_records() {
+            return None;
+        }
+
+        let record = self.reader.read_record(self.next_record);
+        self.next_record = match record {
+            Ok(_) => self.next_record + 1,
+            Err(_) => self.reader.num_records(),
+        };
+
+        Some(record)
+    }
+}
@@ -0,0 +1,238 @@
+use num::complex::Complex;
+
+use crate::{
+    byte_to_i8,
+    bytes_to_complex_f32,
+    bytes_to_complex_f64,
+    bytes_to_complex_i8,
+    bytes_to_complex_u8,
+    bytes_to_complex_u16,
+    bytes_to_complex_u32,
+    bytes_to_complex_i16,
+    bytes_to_complex_i32,
+    bytes_to_complex_i64,
+    bytes_to_f32,
+    bytes_to_f64,
+    bytes_to_i16,
+    bytes_to_i32,
+    bytes_to_i64,
+    bytes_to_u16,
+    bytes_to_u32,
+    DataType,
+    Endianness,
+    Result,
+};
+
+/// A type that samples in the data section of a bluefile can be decoded into.
+pub trait Sample: Copy {
+    /// The rank and format of the data this type is decoded from.
+    fn data_type() -> DataType;
+
+    /// Decodes a single sample from raw bytes.
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self>;
+}
+
+/// A real sample type, which can also be a component of vectors, matrices and multi-channel
+/// elements.
+pub trait Scalar: Sample {}
+
+impl Scalar for i8 {}
+impl Scalar for i16 {}
+impl Scalar for i32 {}
+impl Scalar for i64 {}
+impl Scalar for u8 {}
+impl Scalar for u16 {}
+impl Scalar for u32 {}
+impl Scalar for f32 {}
+impl Scalar for f64 {}
+
+impl Sample for i8 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'B'}
+    }
+
+    fn from_bytes(v: &[u8], _endianness: Endianness) -> Result<Self> {
+        byte_to_i8(v[0])
+    }
+}
+
+impl Sample for i16 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'I'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_i16(v, endianness)
+    }
+}
+
+impl Sample for i32 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'L'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_i32(v, endianness)
+    }
+}
+
+impl Sample for u8 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'O'}
+    }
+
+    fn from_bytes(v: &[u8], _endianness: Endianness) -> Result<Self> {
+        Ok(v[0])
+    }
+}
+
+impl Sample for u16 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'U'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_u16(v, endianness)
+    }
+}
+
+impl Sample for u32 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'V'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_u32(v, endianness)
+    }
+}
+
+impl Sample for i64 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'X'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_i64(v, endianness)
+    }
+}
+
+impl Sample for f32 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'F'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_f32(v, endianness)
+    }
+}
+
+impl Sample for f64 {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'D'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_f64(v, endianness)
+    }
+}
+
+impl Sample for Complex<i8> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'B'}
+    }
+
+    fn from_bytes(v: &[u8], _endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_i8(v)
+    }
+}
+
+impl Sample for Complex<i16> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'I'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_i16(v, endianness)
+    }
+}
+
+impl Sample for Complex<i32> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'L'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_i32(v, endianness)
+    }
+}
+
+impl Sample for Complex<u8> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'O'}
+    }
+
+    fn from_bytes(v: &[u8], _endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_u8(v)
+    }
+}
+
+impl Sample for Complex<u16> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'U'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_u16(v, endianness)
+    }
+}
+
+impl Sample for Complex<u32> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'V'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_u32(v, endianness)
+    }
+}
+
+impl Sample for Complex<i64> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'X'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_i64(v, endianness)
+    }
+}
+
+impl Sample for Complex<f32> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'F'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_f32(v, endianness)
+    }
+}
+
+impl Sample for Complex<f64> {
+    fn data_type() -> DataType {
+        DataType{rank: b'C', format: b'D'}
+    }
+
+    fn from_bytes(v: &[u8], endianness: Endianness) -> Result<Self> {
+        bytes_to_complex_f64(v, endianness)
+    }
+}
+
+/// ASCII (A) data, one character per byte.
+impl Sample for char {
+    fn data_type() -> DataType {
+        DataType{rank: b'S', format: b'A'}
+    }
+
+    fn from_bytes(v: &[u8], _endianness: Endianness) -> Result<Self> {
+        Ok(v[0] as char)
+    }
+}
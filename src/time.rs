@@ -0,0 +1,221 @@
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::ops::Range;
+
+use crate::{
+    Axis,
+    data::check_data_type,
+    data::read_samples,
+    detached::check_attached,
+    Error,
+    ExtKeyword,
+    Header,
+    Result,
+    Sample,
+    Timestamp,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Type2000Reader,
+    Units,
+};
+
+/// Fraction of a step that a time may be off by and still land on a sample, to absorb rounding
+/// in the time arithmetic.
+const STEP_TOLERANCE: f64 = 1e-6;
+
+/// Describes a regularly sampled time axis whose first step is `start` seconds after `origin`.
+/// Times are measured from the origin so that sub-microsecond steps survive the large J1950
+/// timecodes of recent files.
+struct TimeAxis {
+    origin: Timestamp,
+    start: f64,
+    delta: f64,
+    len: usize,
+}
+
+impl TimeAxis {
+    fn new(origin: Timestamp, axis: Axis) -> Result<Self> {
+        if axis.units != Units::Time {
+            return Err(Error::NotTimeAxisError(axis.units));
+        }
+
+        if axis.delta.is_nan() || axis.delta <= 0.0 {
+            return Err(Error::InvalidTimeDeltaError(axis.delta));
+        }
+
+        Ok(TimeAxis{
+            origin,
+            start: axis.start,
+            delta: axis.delta,
+            len: axis.len,
+        })
+    }
+
+    /// Absolute time of step `n`.
+    fn time(&self, n: usize) -> Timestamp {
+        self.origin.add_seconds(self.start + (n as f64)*self.delta)
+    }
+
+    /// Fractional number of steps from the start of the axis to `t`, snapped to a whole step
+    /// when it is within the tolerance of one.
+    fn steps(&self, t: &Timestamp) -> f64 {
+        let x = (t.seconds_since(&self.origin) - self.start)/self.delta;
+        let rounded = x.round();
+
+        if (x - rounded).abs() < STEP_TOLERANCE {
+            rounded
+        } else {
+            x
+        }
+    }
+
+    /// Index of the step that covers `t`.
+    fn index(&self, t: &Timestamp) -> Result<usize> {
+        let x = self.steps(t).floor();
+
+        if x < 0.0 || x >= self.len as f64 {
+            return Err(Error::TimeOutOfRangeError(t.j1950_seconds()));
+        }
+
+        Ok(x as usize)
+    }
+
+    /// Indices of the steps that start at or after `t0` and before `t1`, clipped to the axis.
+    fn range(&self, t0: &Timestamp, t1: &Timestamp) -> Range<usize> {
+        let clip = |t| self.steps(t).ceil().clamp(0.0, self.len as f64) as usize;
+        let start = clip(t0);
+        let end = clip(t1);
+        start..end.max(start)
+    }
+}
+
+fn type1000_axis(origin: Timestamp, header: &Header, adjunct: &Type1000Adjunct) -> Result<TimeAxis> {
+    TimeAxis::new(origin, adjunct.x_axis(header)?)
+}
+
+fn type2000_axis(origin: Timestamp, header: &Header, adjunct: &Type2000Adjunct) -> Result<TimeAxis> {
+    TimeAxis::new(origin, adjunct.y_axis(header)?)
+}
+
+fn header_origin(header: &Header) -> Timestamp {
+    Timestamp::from_j1950(header.timecode)
+}
+
+impl Type1000Adjunct {
+    /// Absolute time of sample `n` in seconds since J1950.  Fails unless the x axis is time.
+    pub fn sample_time(&self, header: &Header, n: usize) -> Result<f64> {
+        Ok(type1000_axis(header_origin(header), header, self)?.time(n).j1950_seconds())
+    }
+
+    /// Index of the sample that covers the absolute time `t`, given in seconds since J1950.
+    pub fn sample_at_time(&self, header: &Header, t: f64) -> Result<usize> {
+        type1000_axis(header_origin(header), header, self)?.index(&Timestamp::from_j1950(t))
+    }
+
+    /// Indices of the samples from `t0` up to, but not including, `t1`.  The range is clipped
+    /// to the samples in the file, so it is empty when the window misses the file entirely.
+    pub fn samples_in_time_range(&self, header: &Header, t0: f64, t1: f64) -> Result<Range<usize>> {
+        Ok(type1000_axis(header_origin(header), header, self)?.range(&Timestamp::from_j1950(t0), &Timestamp::from_j1950(t1)))
+    }
+
+    /// Timestamp of sample `n`, including the `TC_PREC` keyword when it is in `keywords`.
+    pub fn sample_timestamp(&self, header: &Header, keywords: &[ExtKeyword], n: usize) -> Result<Timestamp> {
+        Ok(type1000_axis(Timestamp::from_header(header, keywords)?, header, self)?.time(n))
+    }
+
+    /// Index of the sample that covers the timestamp `t`, including the `TC_PREC` keyword when it
+    /// is in `keywords`.
+    pub fn sample_at_timestamp(&self, header: &Header, keywords: &[ExtKeyword], t: Timestamp) -> Result<usize> {
+        type1000_axis(Timestamp::from_header(header, keywords)?, header, self)?.index(&t)
+    }
+
+    /// Indices of the samples from `t0` up to, but not including, `t1`, clipped to the samples in
+    /// the file.
+    pub fn samples_in_timestamp_range(&self, header: &Header, keywords: &[ExtKeyword], t0: Timestamp, t1: Timestamp) -> Result<Range<usize>> {
+        Ok(type1000_axis(Timestamp::from_header(header, keywords)?, header, self)?.range(&t0, &t1))
+    }
+}
+
+impl Type2000Adjunct {
+    /// Absolute time of frame `n` in seconds since J1950.  Fails unless the y axis is time.
+    pub fn frame_time(&self, header: &Header, n: usize) -> Result<f64> {
+        Ok(type2000_axis(header_origin(header), header, self)?.time(n).j1950_seconds())
+    }
+
+    /// Index of the frame that covers the absolute time `t`, given in seconds since J1950.
+    pub fn frame_at_time(&self, header: &Header, t: f64) -> Result<usize> {
+        type2000_axis(header_origin(header), header, self)?.index(&Timestamp::from_j1950(t))
+    }
+
+    /// Indices of the frames from `t0` up to, but not including, `t1`.  The range is clipped
+    /// to the frames in the file, so it is empty when the window misses the file entirely.
+    pub fn frames_in_time_range(&self, header: &Header, t0: f64, t1: f64) -> Result<Range<usize>> {
+        Ok(type2000_axis(header_origin(header), header, self)?.range(&Timestamp::from_j1950(t0), &Timestamp::from_j1950(t1)))
+    }
+
+    /// Timestamp of frame `n`, including the `TC_PREC` keyword when it is in `keywords`.
+    pub fn frame_timestamp(&self, header: &Header, keywords: &[ExtKeyword], n: usize) -> Result<Timestamp> {
+        Ok(type2000_axis(Timestamp::from_header(header, keywords)?, header, self)?.time(n))
+    }
+
+    /// Index of the frame that covers the timestamp `t`, including the `TC_PREC` keyword when it
+    /// is in `keywords`.
+    pub fn frame_at_timestamp(&self, header: &Header, keywords: &[ExtKeyword], t: Timestamp) -> Result<usize> {
+        type2000_axis(Timestamp::from_header(header, keywords)?, header, self)?.index(&t)
+    }
+
+    /// Indices of the frames from `t0` up to, but not including, `t1`, clipped to the frames in
+    /// the file.
+    pub fn frames_in_timestamp_range(&self, header: &Header, keywords: &[ExtKeyword], t0: Timestamp, t1: Timestamp) -> Result<Range<usize>> {
+        Ok(type2000_axis(Timestamp::from_header(header, keywords)?, header, self)?.range(&t0, &t1))
+    }
+}
+
+/// Reads the samples of a type 1000 file in a range of sample indices.
+fn read_sample_range<T: Sample, R: Read + Seek>(mut reader: R, header: &Header, range: Range<usize>) -> Result<Vec<T>> {
+    let sample_size = check_data_type::<T>(header)?;
+
+    if range.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let offset = header.data_start as u64 + (range.start * sample_size) as u64;
+    if reader.seek(SeekFrom::Start(offset)).is_err() {
+        return Err(Error::DataSeekError);
+    }
+
+    read_samples(&mut reader, range.len(), sample_size, header.data_endianness)
+}
+
+/// Reads the samples of a type 1000 file from the absolute time `t0` up to, but not including,
+/// `t1`.  Times are in seconds since J1950.
+pub fn read_time_range<T: Sample, R: Read + Seek>(reader: R, header: &Header, adjunct: &Type1000Adjunct, t0: f64, t1: f64) -> Result<Vec<T>> {
+    check_data_type::<T>(header)?;
+    check_attached(header)?;
+    let range = adjunct.samples_in_time_range(header, t0, t1)?;
+    read_sample_range(reader, header, range)
+}
+
+/// Reads the samples of a type 1000 file from the timestamp `t0` up to, but not including, `t1`,
+/// including the `TC_PREC` keyword when it is in `keywords`.
+pub fn read_timestamp_range<T: Sample, R: Read + Seek>(reader: R, header: &Header, keywords: &[ExtKeyword], adjunct: &Type1000Adjunct, t0: Timestamp, t1: Timestamp) -> Result<Vec<T>> {
+    check_data_type::<T>(header)?;
+    check_attached(header)?;
+    let range = adjunct.samples_in_timestamp_range(header, keywords, t0, t1)?;
+    read_sample_range(reader, header, range)
+}
+
+/// Reads the frames of a type 2000 file from the absolute time `t0` up to, but not including,
+/// `t1`.  Times are in seconds since J1950.
+pub fn read_frame_time_range<T: Sample, R: Read + Seek>(reader: R, header: &Header, adjunct: &Type2000Adjunct, t0: f64, t1: f64) -> Result<Vec<Vec<T>>> {
+    let range = adjunct.frames_in_time_range(header, t0, t1)?;
+    Type2000Reader::new(reader, header, adjunct)?.read_frames(range)
+}
+
+/// Reads the frames of a type 2000 file from the timestamp `t0` up to, but not including, `t1`,
+/// including the `TC_PREC` keyword when it is in `keywords`.
+pub fn read_frame_timestamp_range<T: Sample, R: Read + Seek>(reader: R, header: &Header, keywords: &[ExtKeyword], adjunct: &Type2000Adjunct, t0: Timestamp, t1: Timestamp) -> Result<Vec<Vec<T>>> {
+    let range = adjunct.frames_in_timestamp_range(header, keywords, t0, t1)?;
+    Type2000Reader::new(reader, header, adjunct)?.read_frames(range)
+}
@@ -0,0 +1,300 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use crate::{
+    Error,
+    ExtKeyword,
+    ExtKeywordValue,
+    Header,
+    Result,
+};
+
+/// Seconds from 1950-01-01 to the Unix epoch, 1970-01-01.
+const J1950_TO_UNIX: i64 = 631_152_000;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Furthest a timestamp can be from J1950, about 285 million years.  Keeping the whole seconds
+/// this small means converting and moving them can't overflow, however corrupt the timecode.
+const MAX_SECONDS: i64 = 1 << 53;
+
+/// Number of fractional digits written out for ISO-8601 strings, enough for picoseconds.
+const ISO_FRACTION_DIGITS: u32 = 12;
+
+/// Extended header keyword holding the part of the timecode that does not fit in the header.
+const TC_PREC_TAG: &str = "TC_PREC";
+
+/// An absolute UTC time, kept as whole seconds since J1950 (1950-01-01T00:00:00Z) plus a fraction
+/// of a second so that sub-microsecond precision survives.  Leap seconds are not counted, as with
+/// X-Midas and Unix time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timestamp {
+    seconds: i64,
+    fraction: f64,
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era*400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153*mp + 2)/5 + day as i64 - 1;
+    let doe = yoe*365 + yoe/4 - yoe/100 + doy;
+    era*146_097 + doe - 719_468
+}
+
+/// Date in the proleptic Gregorian calendar of a number of days since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era*146_097;
+    let yoe = (doe - doe/1460 + doe/36_524 - doe/146_096)/365;
+    let doy = doe - (365*yoe + yoe/4 - yoe/100);
+    let mp = (5*doy + 2)/153;
+    let day = (doy - (153*mp + 2)/5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era*400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Decodes a keyword value holding a number of seconds, either as a number or as text.
+fn keyword_seconds(value: &ExtKeywordValue) -> Result<f64> {
+    match value.as_str() {
+        Ok(s) => match s.trim_end_matches('\0').trim().parse::<f64>() {
+            Ok(x) => Ok(x),
+            Err(_) => Err(Error::ValueConversionError),
+        },
+        Err(_) => value.as_f64(),
+    }
+}
+
+impl Timestamp {
+    /// Creates a timestamp from whole seconds since J1950 and a fraction of a second.  The
+    /// fraction may be outside of [0, 1) and is carried into the seconds.  Times beyond about 285
+    /// million years of J1950 are clamped, and a fraction that is not finite counts as zero.
+    pub fn new(seconds: i64, fraction: f64) -> Self {
+        if !fraction.is_finite() {
+            return Timestamp::new(seconds, 0.0);
+        }
+
+        let carry = fraction.floor();
+
+        Timestamp{
+            seconds: seconds.saturating_add(carry as i64).clamp(-MAX_SECONDS, MAX_SECONDS),
+            fraction: fraction - carry,
+        }
+    }
+
+    /// Creates a timestamp from seconds since J1950, as stored in `Header::timecode`.
+    pub fn from_j1950(seconds: f64) -> Self {
+        Timestamp::new(0, seconds)
+    }
+
+    /// Creates a timestamp from seconds since the Unix epoch.
+    pub fn from_unix(seconds: f64) -> Self {
+        Timestamp::new(J1950_TO_UNIX, seconds)
+    }
+
+    /// Timestamp of the first sample of a file.  The `TC_PREC` extended header keyword, when
+    /// present, holds the part of the timecode that is too fine for `Header::timecode`.
+    pub fn from_header(header: &Header, keywords: &[ExtKeyword]) -> Result<Self> {
+        let whole = header.timecode.floor();
+        let mut fraction = header.timecode - whole;
+
+        if let Some(keyword) = keywords.iter().find(|k| k.tag == TC_PREC_TAG) {
+            fraction += keyword_seconds(&keyword.value)?;
+        }
+
+        Ok(Timestamp::new(whole as i64, fraction))
+    }
+
+    /// Parses an ISO-8601 UTC time such as `2024-03-01T12:30:00.25Z`.  The `Z` may be left off or
+    /// given as `+00:00`, and the `T` may be a space.
+    pub fn from_iso8601(s: &str) -> Result<Self> {
+        let parse_error = || Error::TimestampParseError(s.to_string());
+
+        let trimmed = s.trim();
+        let trimmed = trimmed.strip_suffix('Z')
+            .or_else(|| trimmed.strip_suffix("+00:00"))
+            .unwrap_or(trimmed);
+
+        let (date, time) = match trimmed.split_once(['T', ' ']) {
+            Some(x) => x,
+            None => return Err(parse_error()),
+        };
+
+        let date: Vec<&str> = date.split('-').collect();
+        let time: Vec<&str> = time.split(':').collect();
+        if date.len() != 3 || time.len() != 3 {
+            return Err(parse_error());
+        }
+
+        let (second, fraction) = match time[2].split_once('.') {
+            Some((s, f)) if !f.is_empty() && f.bytes().all(|b| b.is_ascii_digit()) => (s, format!("0.{}", f)),
+            Some(_) => return Err(parse_error()),
+            None => (time[2], "0".to_string()),
+        };
+
+        let year: i64 = date[0].parse().map_err(|_| parse_error())?;
+        let month: u32 = date[1].parse().map_err(|_| parse_error())?;
+        let day: u32 = date[2].parse().map_err(|_| parse_error())?;
+        let hour: i64 = time[0].parse().map_err(|_| parse_error())?;
+        let minute: i64 = time[1].parse().map_err(|_| parse_error())?;
+        let second: i64 = second.parse().map_err(|_| parse_error())?;
+        let fraction: f64 = fraction.parse().map_err(|_| parse_error())?;
+
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) {
+            return Err(parse_error());
+        }
+
+        if !(0..24).contains(&hour) || !(0..60).contains(&minute) || !(0..60).contains(&second) {
+            return Err(parse_error());
+        }
+
+        let days = days_from_civil(year, month, day);
+        let seconds = days*SECONDS_PER_DAY + hour*3600 + minute*60 + second + J1950_TO_UNIX;
+        Ok(Timestamp::new(seconds, fraction))
+    }
+
+    /// Whole seconds since J1950.
+    pub fn seconds(&self) -> i64 {
+        self.seconds
+    }
+
+    /// Fraction of a second past `seconds`, in [0, 1).
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// Seconds since J1950, as stored in `Header::timecode`.
+    pub fn j1950_seconds(&self) -> f64 {
+        self.seconds as f64 + self.fraction
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn unix_seconds(&self) -> f64 {
+        (self.seconds - J1950_TO_UNIX) as f64 + self.fraction
+    }
+
+    /// This timestamp moved by a number of seconds, which may be negative.
+    pub fn add_seconds(&self, seconds: f64) -> Self {
+        let whole = seconds.trunc();
+        Timestamp::new(self.seconds.saturating_add(whole as i64), self.fraction + (seconds - whole))
+    }
+
+    /// Seconds from `earlier` to this timestamp, which is negative when `earlier` is later.  The
+    /// whole seconds are subtracted separately so the result keeps the precision of the fractions.
+    pub fn seconds_since(&self, earlier: &Timestamp) -> f64 {
+        (self.seconds - earlier.seconds) as f64 + (self.fraction - earlier.fraction)
+    }
+
+    /// Rounds the fraction to `digits` decimal places, returning whole seconds since J1950 and
+    /// the fraction as an integer count of 10^-digits seconds.
+    fn round_fraction(&self, digits: u32) -> (i64, u64) {
+        let scale = 10_u64.pow(digits);
+        let units = (self.fraction * scale as f64).round() as u64;
+
+        if units >= scale {
+            (self.seconds + 1, units - scale)
+        } else {
+            (self.seconds, units)
+        }
+    }
+
+    /// Formats the timestamp as an ISO-8601 UTC string such as `2024-03-01T12:30:00.25Z`.
+    /// Fractional seconds are written to the picosecond with trailing zeros dropped.
+    pub fn to_iso8601(&self) -> String {
+        let (seconds, picos) = self.round_fraction(ISO_FRACTION_DIGITS);
+        let unix_seconds = seconds - J1950_TO_UNIX;
+        let (year, month, day) = civil_from_days(unix_seconds.div_euclid(SECONDS_PER_DAY));
+        let time_of_day = unix_seconds.rem_euclid(SECONDS_PER_DAY);
+
+        let mut s = format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            year, month, day, time_of_day/3600, (time_of_day % 3600)/60, time_of_day % 60);
+
+        if picos > 0 {
+            let fraction = format!("{:0width$}", picos, width = ISO_FRACTION_DIGITS as usize);
+            s.push('.');
+            s.push_str(fraction.trim_end_matches('0'));
+        }
+
+        s.push('Z');
+        s
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_iso8601())
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Timestamp::from_iso8601(s)
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(t: SystemTime) -> Self {
+        match t.duration_since(UNIX_EPOCH) {
+            Ok(d) => Timestamp::new(J1950_TO_UNIX + d.as_secs() as i64, d.subsec_nanos() as f64/1e9),
+            Err(e) => {
+                let d = e.duration();
+                Timestamp::new(J1950_TO_UNIX - d.as_secs() as i64, -(d.subsec_nanos() as f64)/1e9)
+            },
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    /// Converts to a system time, rounding to the nearest nanosecond.
+    fn from(t: Timestamp) -> Self {
+        let (seconds, nanos) = t.round_fraction(9);
+        let unix_seconds = seconds - J1950_TO_UNIX;
+        let nanos = Duration::from_nanos(nanos);
+
+        if unix_seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(unix_seconds as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(unix_seconds.unsigned_abs()) + nanos
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(t: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp::new(J1950_TO_UNIX + t.timestamp(), t.timestamp_subsec_nanos() as f64/1e9)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = Error;
+
+    /// Converts to a chrono UTC time, rounding to the nearest nanosecond.  Fails when the time is
+    /// outside of the range chrono can represent.
+    fn try_from(t: Timestamp) -> Result<Self> {
+        let (seconds, nanos) = t.round_fraction(9);
+
+        match chrono::DateTime::from_timestamp(seconds - J1950_TO_UNIX, nanos as u32) {
+            Some(x) => Ok(x),
+            None => Err(Error::ValueConversionError),
+        }
+    }
+}
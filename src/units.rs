@@ -0,0 +1,199 @@
+use std::fmt;
+
+use crate::{
+    Type1000Adjunct,
+    Type2000Adjunct,
+};
+
+/// Units of an axis or field, decoded from the X-Midas units codes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Units {
+    None,
+    Time,
+    Delay,
+    Frequency,
+    TimeCode,
+    Distance,
+    Speed,
+    Acceleration,
+    Jerk,
+    Doppler,
+    DopplerRate,
+    Energy,
+    Power,
+    Mass,
+    Volume,
+    AngularPowerDensity,
+    IntegratedAngularPowerDensity,
+    SpatialPowerDensity,
+    IntegratedSpatialPowerDensity,
+    SpectralPowerDensity,
+    Amplitude,
+    Real,
+    Imaginary,
+    PhaseRadians,
+    PhaseDegrees,
+    PhaseCycles,
+    TenLog,
+    TwentyLog,
+    Magnitude,
+    Dimensionless,
+    Counts,
+    AngleRadians,
+    AngleDegrees,
+    RelativePower,
+    RelativePowerMilliwatts,
+    RelativePowerWatts,
+    SolidAngle,
+    DistanceFeet,
+    DistanceNauticalMiles,
+    SpeedFeet,
+    SpeedNauticalMiles,
+    Knots,
+    AccelerationFeet,
+    AccelerationNauticalMiles,
+    KnotsPerSecond,
+    AccelerationG,
+    JerkG,
+    RotationsPerSecond,
+    RotationsPerMinute,
+    AngularVelocityRadians,
+    AngularVelocityDegrees,
+    AngularAccelerationRadians,
+    AngularAccelerationDegrees,
+    Latitude,
+    Longitude,
+    AltitudeFeet,
+    AltitudeMeters,
+
+    /// A code that is not in the units table.
+    Unknown(i32),
+}
+
+/// The X-Midas units table: code, units, name and abbreviation.
+const UNITS_TABLE: [(i32, Units, &str, &str); 57] = [
+    (0, Units::None, "None", ""),
+    (1, Units::Time, "Time", "sec"),
+    (2, Units::Delay, "Delay", "sec"),
+    (3, Units::Frequency, "Frequency", "Hz"),
+    (4, Units::TimeCode, "Time code format", ""),
+    (5, Units::Distance, "Distance", "m"),
+    (6, Units::Speed, "Speed", "m/sec"),
+    (7, Units::Acceleration, "Acceleration", "m/sec^2"),
+    (8, Units::Jerk, "Jerk", "m/sec^3"),
+    (9, Units::Doppler, "Doppler", "Hz"),
+    (10, Units::DopplerRate, "Doppler rate", "Hz/sec"),
+    (11, Units::Energy, "Energy", "J"),
+    (12, Units::Power, "Power", "W"),
+    (13, Units::Mass, "Mass", "g"),
+    (14, Units::Volume, "Volume", "l"),
+    (15, Units::AngularPowerDensity, "Angular power density", "W/ster"),
+    (16, Units::IntegratedAngularPowerDensity, "Integrated power density", "W/rad"),
+    (17, Units::SpatialPowerDensity, "Spatial power density", "W/m^2"),
+    (18, Units::IntegratedSpatialPowerDensity, "Integrated power density", "W/m"),
+    (19, Units::SpectralPowerDensity, "Spectral power density", "W/MHz"),
+    (20, Units::Amplitude, "Amplitude", ""),
+    (21, Units::Real, "Real", ""),
+    (22, Units::Imaginary, "Imaginary", ""),
+    (23, Units::PhaseRadians, "Phase", "rad"),
+    (24, Units::PhaseDegrees, "Phase", "deg"),
+    (25, Units::PhaseCycles, "Phase", "cycles"),
+    (26, Units::TenLog, "10*Log", ""),
+    (27, Units::TwentyLog, "20*Log", ""),
+    (28, Units::Magnitude, "Magnitude", ""),
+    (31, Units::Dimensionless, "General dimensionless", ""),
+    (32, Units::Counts, "Counts", ""),
+    (33, Units::AngleRadians, "Angle", "rad"),
+    (34, Units::AngleDegrees, "Angle", "deg"),
+    (35, Units::RelativePower, "Relative power", "dB"),
+    (36, Units::RelativePowerMilliwatts, "Relative power", "dBm"),
+    (37, Units::RelativePowerWatts, "Relative power", "dBW"),
+    (38, Units::SolidAngle, "Solid angle", "ster"),
+    (40, Units::DistanceFeet, "Distance", "ft"),
+    (41, Units::DistanceNauticalMiles, "Distance", "nmi"),
+    (42, Units::SpeedFeet, "Speed", "ft/sec"),
+    (43, Units::SpeedNauticalMiles, "Speed", "nmi/sec"),
+    (44, Units::Knots, "Speed", "knots"),
+    (45, Units::AccelerationFeet, "Acceleration", "ft/sec^2"),
+    (46, Units::AccelerationNauticalMiles, "Acceleration", "nmi/sec^2"),
+    (47, Units::KnotsPerSecond, "Acceleration", "knots/sec"),
+    (48, Units::AccelerationG, "Acceleration", "G"),
+    (49, Units::JerkG, "Jerk", "G/sec"),
+    (50, Units::RotationsPerSecond, "Rotation", "rps"),
+    (51, Units::RotationsPerMinute, "Rotation", "rpm"),
+    (52, Units::AngularVelocityRadians, "Angular velocity", "rad/sec"),
+    (53, Units::AngularVelocityDegrees, "Angular velocity", "deg/sec"),
+    (54, Units::AngularAccelerationRadians, "Angular acceleration", "rad/sec^2"),
+    (55, Units::AngularAccelerationDegrees, "Angular acceleration", "deg/sec^2"),
+    (60, Units::Latitude, "Latitude", "deg"),
+    (61, Units::Longitude, "Longitude", "deg"),
+    (62, Units::AltitudeFeet, "Altitude", "ft"),
+    (63, Units::AltitudeMeters, "Altitude", "m"),
+];
+
+impl Units {
+    fn entry(&self) -> Option<&'static (i32, Units, &'static str, &'static str)> {
+        UNITS_TABLE.iter().find(|e| e.1 == *self)
+    }
+
+    /// Units code as stored in the file.
+    pub fn code(&self) -> i32 {
+        match self {
+            Units::Unknown(code) => *code,
+            _ => self.entry().map_or(0, |e| e.0),
+        }
+    }
+
+    /// Human-readable name, such as "Frequency".
+    pub fn name(&self) -> &'static str {
+        self.entry().map_or("Unknown", |e| e.2)
+    }
+
+    /// Abbreviation of the units, such as "Hz".  Dimensionless units have an empty abbreviation.
+    pub fn abbreviation(&self) -> &'static str {
+        self.entry().map_or("", |e| e.3)
+    }
+}
+
+impl From<i32> for Units {
+    fn from(code: i32) -> Self {
+        match UNITS_TABLE.iter().find(|e| e.0 == code) {
+            Some(e) => e.1,
+            None => Units::Unknown(code),
+        }
+    }
+}
+
+impl From<Units> for i32 {
+    fn from(units: Units) -> Self {
+        units.code()
+    }
+}
+
+impl fmt::Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.abbreviation() {
+            "" => f.write_str(self.name()),
+            abbreviation => write!(f, "{} ({})", self.name(), abbreviation),
+        }
+    }
+}
+
+impl Type1000Adjunct {
+    /// Decoded units of the x axis.
+    pub fn x_units(&self) -> Units {
+        Units::from(self.xunits)
+    }
+}
+
+impl Type2000Adjunct {
+    /// Decoded units of the x axis.
+    pub fn x_units(&self) -> Units {
+        Units::from(self.xunits)
+    }
+
+    /// Decoded units of the y axis.
+    pub fn y_units(&self) -> Units {
+        Units::from(self.yunits)
+    }
+}
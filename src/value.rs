@@ -0,0 +1,181 @@
+use std::fmt;
+
+use num::complex::Complex;
+
+use crate::{
+    byte_to_i8,
+    bytes_to_complex_f32,
+    bytes_to_complex_f64,
+    bytes_to_complex_i8,
+    bytes_to_complex_u8,
+    bytes_to_complex_u16,
+    bytes_to_complex_u32,
+    bytes_to_complex_i16,
+    bytes_to_complex_i32,
+    bytes_to_complex_i64,
+    bytes_to_f32,
+    bytes_to_f64,
+    bytes_to_i16,
+    bytes_to_i32,
+    bytes_to_i64,
+    bytes_to_u16,
+    bytes_to_u32,
+    DataType,
+    Endianness,
+    Error,
+    Result,
+};
+
+/// A single decoded value, such as a field of a record.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    F32(f32),
+    F64(f64),
+    ComplexI8(Complex<i8>),
+    ComplexI16(Complex<i16>),
+    ComplexI32(Complex<i32>),
+    ComplexI64(Complex<i64>),
+    ComplexU8(Complex<u8>),
+    ComplexU16(Complex<u16>),
+    ComplexU32(Complex<u32>),
+    ComplexF32(Complex<f32>),
+    ComplexF64(Complex<f64>),
+    String(String),
+    Array(Vec<Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::I8(x) => write!(f, "{}", x),
+            Value::I16(x) => write!(f, "{}", x),
+            Value::I32(x) => write!(f, "{}", x),
+            Value::I64(x) => write!(f, "{}", x),
+            Value::U8(x) => write!(f, "{}", x),
+            Value::U16(x) => write!(f, "{}", x),
+            Value::U32(x) => write!(f, "{}", x),
+            Value::F32(x) => write!(f, "{}", x),
+            Value::F64(x) => write!(f, "{}", x),
+            Value::ComplexI8(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexI16(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexI32(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexI64(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexU8(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexU16(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexU32(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexF32(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::ComplexF64(x) => write!(f, "[{}, {}]", x.re, x.im),
+            Value::String(x) => write!(f, "\"{}\"", x.replace('\"', "\\\"")),
+            Value::Array(x) => {
+                let items: Vec<String> = x.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            },
+        }
+    }
+}
+
+impl Value {
+    /// Decodes a single element of the given data type from raw bytes.
+    pub fn decode(data_type: &DataType, v: &[u8], endianness: Endianness) -> Result<Value> {
+        let size = data_type.num_bytes()?;
+
+        if v.len() < size {
+            return Err(Error::ByteConversionError);
+        }
+
+        let v = &v[0..size];
+
+        if !matches!(data_type.rank, b'S' | b'C') {
+            return Value::decode_components(data_type, v, endianness);
+        }
+
+        match (data_type.rank, data_type.format) {
+            (b'S', b'A') => Ok(Value::String((v[0] as char).to_string())),
+            (b'S', b'B') => Ok(Value::I8(byte_to_i8(v[0])?)),
+            (b'S', b'O') => Ok(Value::U8(v[0])),
+            (b'S', b'I') => Ok(Value::I16(bytes_to_i16(v, endianness)?)),
+            (b'S', b'U') => Ok(Value::U16(bytes_to_u16(v, endianness)?)),
+            (b'S', b'L') => Ok(Value::I32(bytes_to_i32(v, endianness)?)),
+            (b'S', b'V') => Ok(Value::U32(bytes_to_u32(v, endianness)?)),
+            (b'S', b'X') => Ok(Value::I64(bytes_to_i64(v, endianness)?)),
+            (b'S', b'F') => Ok(Value::F32(bytes_to_f32(v, endianness)?)),
+            (b'S', b'D') => Ok(Value::F64(bytes_to_f64(v, endianness)?)),
+            (b'C', b'B') => Ok(Value::ComplexI8(bytes_to_complex_i8(v)?)),
+            (b'C', b'O') => Ok(Value::ComplexU8(bytes_to_complex_u8(v)?)),
+            (b'C', b'I') => Ok(Value::ComplexI16(bytes_to_complex_i16(v, endianness)?)),
+            (b'C', b'U') => Ok(Value::ComplexU16(bytes_to_complex_u16(v, endianness)?)),
+            (b'C', b'L') => Ok(Value::ComplexI32(bytes_to_complex_i32(v, endianness)?)),
+            (b'C', b'V') => Ok(Value::ComplexU32(bytes_to_complex_u32(v, endianness)?)),
+            (b'C', b'X') => Ok(Value::ComplexI64(bytes_to_complex_i64(v, endianness)?)),
+            (b'C', b'F') => Ok(Value::ComplexF32(bytes_to_complex_f32(v, endianness)?)),
+            (b'C', b'D') => Ok(Value::ComplexF64(bytes_to_complex_f64(v, endianness)?)),
+            _ => Err(Error::UnknownDataTypeError),
+        }
+    }
+
+    /// Decodes a multi-component element into an array of its components.  Matrices are decoded
+    /// into an array of rows.
+    fn decode_components(data_type: &DataType, v: &[u8], endianness: Endianness) -> Result<Value> {
+        let scalar = data_type.scalar();
+        let components: Result<Vec<Value>> = v.chunks_exact(scalar.num_bytes()?)
+            .map(|x| Value::decode(&scalar, x, endianness))
+            .collect();
+        let components = components?;
+
+        let row_length = match data_type.rank {
+            b'M' => 3,
+            b'T' => 4,
+            _ => return Ok(Value::Array(components)),
+        };
+
+        let rows = components.chunks_exact(row_length)
+            .map(|x| Value::Array(x.to_vec()))
+            .collect();
+        Ok(Value::Array(rows))
+    }
+
+    /// Converts a real numeric value to an f64.
+    pub fn as_f64(&self) -> Result<f64> {
+        match self {
+            Value::I8(x) => Ok(*x as f64),
+            Value::I16(x) => Ok(*x as f64),
+            Value::I32(x) => Ok(*x as f64),
+            Value::I64(x) => Ok(*x as f64),
+            Value::U8(x) => Ok(*x as f64),
+            Value::U16(x) => Ok(*x as f64),
+            Value::U32(x) => Ok(*x as f64),
+            Value::F32(x) => Ok(*x as f64),
+            Value::F64(x) => Ok(*x),
+            _ => Err(Error::ValueConversionError),
+        }
+    }
+
+    /// Converts a real integer value to an i64.
+    pub fn as_i64(&self) -> Result<i64> {
+        match self {
+            Value::I8(x) => Ok(*x as i64),
+            Value::I16(x) => Ok(*x as i64),
+            Value::I32(x) => Ok(*x as i64),
+            Value::I64(x) => Ok(*x),
+            Value::U8(x) => Ok(*x as i64),
+            Value::U16(x) => Ok(*x as i64),
+            Value::U32(x) => Ok(*x as i64),
+            _ => Err(Error::ValueConversionError),
+        }
+    }
+
+    /// Borrows a string value.
+    pub fn as_str(&self) -> Result<&str> {
+        match self {
+            Value::String(x) => Ok(x),
+            _ => Err(Error::ValueConversionError),
+        }
+    }
+}
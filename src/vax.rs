@@ -0,0 +1,136 @@
+//! Conversions between VAX F, D and G floating point and IEEE floating point.
+//!
+//! VAX floats are stored as little-endian 16-bit words with the most significant word first.  Once
+//! the words are put back in order the layout is sign, exponent and fraction, like IEEE, but the
+//! hidden bit sits to the right of the binary point (0.1f rather than 1.f), exponent zero means
+//! zero, and there are no infinities or denormals.
+
+struct VaxFormat {
+    exp_bits: u32,
+    frac_bits: u32,
+    bias: i32,
+}
+
+const VAX_F: VaxFormat = VaxFormat{exp_bits: 8, frac_bits: 23, bias: 128};
+const VAX_D: VaxFormat = VaxFormat{exp_bits: 8, frac_bits: 55, bias: 128};
+const VAX_G: VaxFormat = VaxFormat{exp_bits: 11, frac_bits: 52, bias: 1024};
+
+fn words_to_bits(v: &[u8]) -> u64 {
+    v.chunks_exact(2).fold(0, |acc, w| (acc << 16) | u16::from_le_bytes([w[0], w[1]]) as u64)
+}
+
+fn bits_to_words<const N: usize>(bits: u64) -> [u8; N] {
+    let mut v = [0_u8; N];
+
+    for (i, w) in v.chunks_exact_mut(2).enumerate() {
+        let shift = 16 * (N/2 - 1 - i);
+        w.copy_from_slice(&((bits >> shift) as u16).to_le_bytes());
+    }
+
+    v
+}
+
+/// Multiplies by a power of two in two steps so that the intermediate value cannot underflow or
+/// overflow when the result is in range.
+fn scale(x: f64, n: i32) -> f64 {
+    x * 2.0_f64.powi(n/2) * 2.0_f64.powi(n - n/2)
+}
+
+/// Splits a positive, finite value into a mantissa in [0.5, 1) and a power of two.
+fn frexp(x: f64) -> (f64, i32) {
+    let bits = x.to_bits();
+    let exp = ((bits >> 52) & 0x7ff) as i32;
+
+    if exp == 0 {
+        // denormal, so normalize it first
+        let (m, e) = frexp(x * 2.0_f64.powi(64));
+        return (m, e - 64);
+    }
+
+    (f64::from_bits((bits & !(0x7ff << 52)) | (1022 << 52)), exp - 1022)
+}
+
+fn decode(bits: u64, format: &VaxFormat) -> f64 {
+    let sign = (bits >> (format.exp_bits + format.frac_bits)) & 1;
+    let exp = ((bits >> format.frac_bits) & ((1 << format.exp_bits) - 1)) as i32;
+    let frac = bits & ((1 << format.frac_bits) - 1);
+
+    if exp == 0 {
+        // zero, or a reserved operand when the sign bit is set
+        return if sign == 0 { 0.0 } else { f64::NAN };
+    }
+
+    let mantissa = ((1 << format.frac_bits) | frac) as f64;
+    let value = scale(mantissa, exp - format.bias - format.frac_bits as i32 - 1);
+
+    if sign == 0 { value } else { -value }
+}
+
+fn encode(x: f64, format: &VaxFormat) -> u64 {
+    let sign_bit = 1 << (format.exp_bits + format.frac_bits);
+
+    if x == 0.0 {
+        return 0;
+    }
+
+    if x.is_nan() {
+        return sign_bit;  // reserved operand
+    }
+
+    let sign = if x.is_sign_negative() { sign_bit } else { 0 };
+    let largest = sign_bit - 1;
+
+    if x.is_infinite() {
+        return sign | largest;
+    }
+
+    let (m, e) = frexp(x.abs());
+    let mut mantissa = scale(m, format.frac_bits as i32 + 1).round() as u64;
+    let mut exp = e + format.bias;
+
+    if mantissa == 1 << (format.frac_bits + 1) {
+        // rounding carried into the next power of two
+        mantissa >>= 1;
+        exp += 1;
+    }
+
+    if exp <= 0 {
+        return 0;
+    }
+
+    if exp >= 1 << format.exp_bits {
+        return sign | largest;
+    }
+
+    sign | ((exp as u64) << format.frac_bits) | (mantissa & ((1 << format.frac_bits) - 1))
+}
+
+/// Converts a VAX F float to an f32.
+pub(crate) fn vax_f_to_f32(v: &[u8; 4]) -> f32 {
+    decode(words_to_bits(v), &VAX_F) as f32
+}
+
+/// Converts a VAX D float to an f64.  The three extra fraction bits are rounded off.
+pub(crate) fn vax_d_to_f64(v: &[u8; 8]) -> f64 {
+    decode(words_to_bits(v), &VAX_D)
+}
+
+/// Converts a VAX G float to an f64.
+pub(crate) fn vax_g_to_f64(v: &[u8; 8]) -> f64 {
+    decode(words_to_bits(v), &VAX_G)
+}
+
+/// Converts an f32 to a VAX F float.  Values out of range are clamped.
+pub(crate) fn f32_to_vax_f(x: f32) -> [u8; 4] {
+    bits_to_words(encode(x as f64, &VAX_F))
+}
+
+/// Converts an f64 to a VAX D float.  Values out of range are clamped.
+pub(crate) fn f64_to_vax_d(x: f64) -> [u8; 8] {
+    bits_to_words(encode(x, &VAX_D))
+}
+
+/// Converts an f64 to a VAX G float.  Values out of range are clamped.
+pub(crate) fn f64_to_vax_g(x: f64) -> [u8; 8] {
+    bits_to_words(encode(x, &VAX_G))
+}
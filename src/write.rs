@@ -0,0 +1,317 @@
+use std::io::Write;
+
+use crate::{
+    Adjunct,
+    Endianness,
+    ephemeris::{
+        TYPE5000_COMPONENT_OFFSET,
+        TYPE5000_COMPONENT_SIZE,
+        TYPE5000_COORD_SYSTEM_LENGTH,
+        TYPE5000_COORD_SYSTEM_OFFSET,
+        TYPE5000_MAX_COMPONENTS,
+    },
+    Error,
+    ExtKeyword,
+    f64_to_bytes,
+    Header,
+    i16_to_bytes,
+    i32_to_bytes,
+    keyword_block_length,
+    Result,
+    ADJUNCT_HEADER_SIZE,
+    COMMON_HEADER_SIZE,
+    HEADER_KEYWORD_LENGTH,
+    HEADER_KEYWORD_OFFSET,
+};
+
+const BLOCK_SIZE: usize = 512;  // in bytes, the unit of ext_start
+
+fn put(v: &mut [u8], offset: usize, bytes: &[u8]) {
+    v[offset..offset+bytes.len()].copy_from_slice(bytes);
+}
+
+/// Copies a name into a fixed length, space padded field.  A name that does not fit produces the
+/// error made by `error`.
+fn put_name(v: &mut [u8], offset: usize, length: usize, name: &str, error: fn(String) -> Error) -> Result<()> {
+    if name.len() > length {
+        return Err(error(name.to_string()));
+    }
+
+    v[offset..offset+length].fill(b' ');
+    put(v, offset, name.as_bytes());
+    Ok(())
+}
+
+/// Converts the main header to its raw 256 byte form.  `keylength` must match the length of the
+/// keywords once they are written out as `NAME=VALUE` pairs, each followed by a null.
+pub fn header_to_bytes(header: &Header) -> Result<Vec<u8>> {
+    if !header.ext_start.is_multiple_of(BLOCK_SIZE) {
+        return Err(Error::InvalidExtStart(header.ext_start));
+    }
+
+    let endianness = header.header_endianness;
+    let mut v = vec![0_u8; COMMON_HEADER_SIZE];
+    put(&mut v, 0, b"BLUE");
+    put(&mut v, 4, header.header_endianness.as_bytes());
+    put(&mut v, 8, header.data_endianness.as_bytes());
+    put(&mut v, 12, &i32_to_bytes(header.detached, endianness));
+    put(&mut v, 16, &i32_to_bytes(header.protected, endianness));
+    put(&mut v, 20, &i32_to_bytes(header.pipe, endianness));
+    put(&mut v, 24, &i32_to_bytes((header.ext_start / BLOCK_SIZE) as i32, endianness));
+    put(&mut v, 28, &i32_to_bytes(header.ext_size as i32, endianness));
+    put(&mut v, 32, &f64_to_bytes(header.data_start, endianness));
+    put(&mut v, 40, &f64_to_bytes(header.data_size, endianness));
+    put(&mut v, 48, &i32_to_bytes(header.type_code, endianness));
+    put(&mut v, 52, &[header.data_type.rank, header.data_type.format]);
+    put(&mut v, 54, &i16_to_bytes(header.flagmask, endianness));
+    put(&mut v, 56, &f64_to_bytes(header.timecode, endianness));
+    put(&mut v, 64, &i16_to_bytes(header.inlet, endianness));
+    put(&mut v, 66, &i16_to_bytes(header.outlets, endianness));
+    put(&mut v, 68, &i32_to_bytes(header.outmask, endianness));
+    put(&mut v, 72, &i32_to_bytes(header.pipeloc, endianness));
+    put(&mut v, 76, &i32_to_bytes(header.pipesize, endianness));
+    put(&mut v, 80, &f64_to_bytes(header.in_byte, endianness));
+    put(&mut v, 88, &f64_to_bytes(header.out_byte, endianness));
+
+    for (i, x) in header.outbytes.iter().enumerate() {
+        put(&mut v, 96 + i*8, &f64_to_bytes(*x, endianness));
+    }
+
+    let mut keywords = Vec::new();
+
+    for keyword in &header.keywords {
+        keywords.extend_from_slice(keyword.name.as_bytes());
+        keywords.push(b'=');
+        keywords.extend_from_slice(keyword.value.as_bytes());
+        keywords.push(b'\0');
+    }
+
+    if keywords.len() > HEADER_KEYWORD_LENGTH {
+        return Err(Error::InvalidHeaderKeywordLength(keywords.len()));
+    }
+
+    if header.keylength != keywords.len() {
+        return Err(Error::HeaderKeywordLengthMismatchError(header.keylength, keywords.len()));
+    }
+
+    put(&mut v, 160, &i32_to_bytes(header.keylength as i32, endianness));
+    put(&mut v, HEADER_KEYWORD_OFFSET, &keywords);
+
+    Ok(v)
+}
+
+/// Converts an adjunct header to its raw 256 byte form.
+pub fn adjunct_to_bytes(adjunct: &Adjunct, endianness: Endianness) -> Result<Vec<u8>> {
+    let mut v = vec![0_u8; ADJUNCT_HEADER_SIZE];
+
+    match adjunct {
+        Adjunct::Type1000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.xstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.xdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.xunits, endianness));
+        },
+        Adjunct::Type2000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.xstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.xdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.xunits, endianness));
+            put(&mut v, 20, &i32_to_bytes(adj.subsize, endianness));
+            put(&mut v, 24, &f64_to_bytes(adj.ystart, endianness));
+            put(&mut v, 32, &f64_to_bytes(adj.ydelta, endianness));
+            put(&mut v, 40, &i32_to_bytes(adj.yunits, endianness));
+        },
+        Adjunct::Type3000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.rstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.rdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.runits, endianness));
+            put(&mut v, 20, &i32_to_bytes(adj.subrecord_table.len() as i32, endianness));
+            put(&mut v, 24, &f64_to_bytes(adj.r2start, endianness));
+            put(&mut v, 32, &f64_to_bytes(adj.r2delta, endianness));
+            put(&mut v, 40, &i32_to_bytes(adj.r2units, endianness));
+            put(&mut v, 44, &i32_to_bytes(adj.record_length, endianness));
+
+            if adj.subrecord_table.len() > 26 {
+                return Err(Error::InvalidSubrecordCount(adj.subrecord_table.len() as i32));
+            }
+
+            for (i, subrecord) in adj.subrecord_table.iter().enumerate() {
+                let offset = 48 + i*8;
+                put_name(&mut v, offset, 4, &subrecord.name, Error::InvalidSubrecordError)?;
+                put(&mut v, offset+4, &[subrecord.data_type.rank, subrecord.data_type.format]);
+                let subrecord_offset = match i16::try_from(subrecord.offset) {
+                    Ok(x) => x,
+                    Err(_) => return Err(Error::InvalidSubrecordError(subrecord.name.clone())),
+                };
+                put(&mut v, offset+6, &i16_to_bytes(subrecord_offset, endianness));
+            }
+        },
+        Adjunct::Type4000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.vrstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.vrdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.vrunits, endianness));
+            put(&mut v, 20, &i32_to_bytes(adj.nrecords, endianness));
+            put(&mut v, 24, &f64_to_bytes(adj.vr2start, endianness));
+            put(&mut v, 32, &f64_to_bytes(adj.vr2delta, endianness));
+            put(&mut v, 40, &i32_to_bytes(adj.vr2units, endianness));
+            put(&mut v, 44, &i32_to_bytes(adj.vrecord_length, endianness));
+        },
+        Adjunct::Type5000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.rstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.rdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.runits, endianness));
+            put(&mut v, 20, &i32_to_bytes(adj.components.len() as i32, endianness));
+            put(&mut v, 24, &f64_to_bytes(adj.r2start, endianness));
+            put(&mut v, 32, &f64_to_bytes(adj.r2delta, endianness));
+            put(&mut v, 40, &i32_to_bytes(adj.r2units, endianness));
+            put(&mut v, 44, &i32_to_bytes(adj.record_length, endianness));
+
+            if adj.components.len() > TYPE5000_MAX_COMPONENTS {
+                return Err(Error::InvalidSubrecordCount(adj.components.len() as i32));
+            }
+
+            for (i, component) in adj.components.iter().enumerate() {
+                let offset = TYPE5000_COMPONENT_OFFSET + i*TYPE5000_COMPONENT_SIZE;
+                put_name(&mut v, offset, 4, &component.name, Error::InvalidSubrecordError)?;
+                put(&mut v, offset+4, &[component.data_type.rank, component.data_type.format]);
+                put(&mut v, offset+6, &[component.kind.into(), component.units]);
+            }
+
+            put_name(&mut v, TYPE5000_COORD_SYSTEM_OFFSET, TYPE5000_COORD_SYSTEM_LENGTH, &adj.coord_system, Error::InvalidCoordSystemError)?;
+        },
+        Adjunct::Type6000(adj) => {
+            put(&mut v, 0, &f64_to_bytes(adj.rstart, endianness));
+            put(&mut v, 8, &f64_to_bytes(adj.rdelta, endianness));
+            put(&mut v, 16, &i32_to_bytes(adj.runits, endianness));
+            put(&mut v, 20, &i32_to_bytes(adj.subrecords, endianness));
+            put(&mut v, 24, &f64_to_bytes(adj.r2start, endianness));
+            put(&mut v, 32, &f64_to_bytes(adj.r2delta, endianness));
+            put(&mut v, 40, &i32_to_bytes(adj.r2units, endianness));
+            put(&mut v, 44, &i32_to_bytes(adj.record_length, endianness));
+            put(&mut v, 48, &i32_to_bytes(adj.subr_offset, endianness));
+        },
+    }
+
+    Ok(v)
+}
+
+/// Converts a keyword to its raw keyword block form, using the endianness of its value.
+pub fn ext_keyword_to_bytes(keyword: &ExtKeyword) -> Result<Vec<u8>> {
+    if keyword.tag.len() > u8::MAX as usize {
+        return Err(Error::InvalidExtKeywordTag(keyword.tag.clone()));
+    }
+
+    let endianness = keyword.value.endianness;
+    let value_length = keyword.value.raw_value.len();
+    let length = keyword_block_length(value_length, keyword.tag.len());
+
+    let mut v = Vec::with_capacity(length);
+    v.extend_from_slice(&i32_to_bytes(length as i32, endianness));
+    v.extend_from_slice(&i16_to_bytes((length - value_length) as i16, endianness));
+    v.push(keyword.tag.len() as u8);
+    v.push(keyword.value.format as u8);
+    v.extend_from_slice(&keyword.value.raw_value);
+    v.extend_from_slice(keyword.tag.as_bytes());
+    v.resize(length, 0);
+
+    Ok(v)
+}
+
+/// Converts keywords to a contiguous run of keyword blocks.
+pub fn ext_keywords_to_bytes(keywords: &[ExtKeyword]) -> Result<Vec<u8>> {
+    let mut v = Vec::new();
+
+    for keyword in keywords {
+        v.extend(ext_keyword_to_bytes(keyword)?);
+    }
+
+    Ok(v)
+}
+
+fn write_bytes<W: Write>(mut writer: W, v: &[u8]) -> Result<()> {
+    match writer.write_all(v) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::FileWriteError),
+    }
+}
+
+/// Writes the main header.
+pub fn write_header<W: Write>(writer: W, header: &Header) -> Result<()> {
+    write_bytes(writer, &header_to_bytes(header)?)
+}
+
+/// Writes the adjunct header.
+pub fn write_adjunct<W: Write>(writer: W, adjunct: &Adjunct, endianness: Endianness) -> Result<()> {
+    write_bytes(writer, &adjunct_to_bytes(adjunct, endianness)?)
+}
+
+/// Writes the extended header keywords, padded to a multiple of 512 bytes.  Returns the number of
+/// bytes of keywords written, which is the header's `ext_size`.
+pub fn write_ext_header<W: Write>(writer: W, keywords: &[ExtKeyword]) -> Result<usize> {
+    let mut v = ext_keywords_to_bytes(keywords)?;
+    let ext_size = v.len();
+    v.resize(ext_size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+    write_bytes(writer, &v)?;
+    Ok(ext_size)
+}
+
+/// Makes sure the keywords can be read back with the header, which reads the keyword blocks in the
+/// header endianness.
+fn check_keyword_endianness(keywords: &[ExtKeyword], endianness: Endianness) -> Result<()> {
+    if keywords.iter().any(|k| k.value.endianness != endianness) {
+        return Err(Error::InvalidEndianness);
+    }
+
+    Ok(())
+}
+
+/// Writes a complete bluefile: the header and adjunct, the data starting at byte 512, then the
+/// extended header keywords.  The locations and sizes of the data and extended header are filled
+/// in from what is written.  The keyword values must have the header endianness.  Returns the
+/// header as written.
+pub fn write_bluefile<W: Write>(mut writer: W, header: &Header, adjunct: &Adjunct, data: &[u8], keywords: &[ExtKeyword]) -> Result<Header> {
+    check_keyword_endianness(keywords, header.header_endianness)?;
+
+    let data_start = COMMON_HEADER_SIZE + ADJUNCT_HEADER_SIZE;
+    let data_end = data_start + data.len();
+
+    let mut header = header.clone();
+    header.detached = 0;
+    header.data_start = data_start as f64;
+    header.data_size = data.len() as f64;
+    header.ext_start = if keywords.is_empty() { 0 } else { data_end.div_ceil(BLOCK_SIZE) * BLOCK_SIZE };
+    header.ext_size = ext_keywords_to_bytes(keywords)?.len();
+
+    write_header(&mut writer, &header)?;
+    write_adjunct(&mut writer, adjunct, header.header_endianness)?;
+    write_bytes(&mut writer, data)?;
+
+    if !keywords.is_empty() {
+        write_bytes(&mut writer, &vec![0_u8; header.ext_start - data_end])?;
+        write_ext_header(&mut writer, keywords)?;
+    }
+
+    Ok(header)
+}
+
+/// Writes a detached pair: the header, adjunct and extended header keywords to one writer, and the
+/// data to another.  The keyword values must have the header endianness.  Returns the header as
+/// written.
+pub fn write_detached<W: Write, D: Write>(mut writer: W, data_writer: D, header: &Header, adjunct: &Adjunct, data: &[u8], keywords: &[ExtKeyword]) -> Result<Header> {
+    check_keyword_endianness(keywords, header.header_endianness)?;
+
+    let mut header = header.clone();
+    header.detached = 1;
+    header.data_start = 0.0;
+    header.data_size = data.len() as f64;
+    header.ext_start = if keywords.is_empty() { 0 } else { COMMON_HEADER_SIZE + ADJUNCT_HEADER_SIZE };
+    header.ext_size = ext_keywords_to_bytes(keywords)?.len();
+
+    write_header(&mut writer, &header)?;
+    write_adjunct(&mut writer, adjunct, header.header_endianness)?;
+
+    if !keywords.is_empty() {
+        write_ext_header(&mut writer, keywords)?;
+    }
+
+    write_bytes(data_writer, data)?;
+    Ok(header)
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use std::fs::File;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bluefile::{
+    Adjunct,
+    read_adjunct,
+    read_header,
+};
+
+use common::{
+    header,
+    header_bytes,
+};
+
+#[test]
+fn read_adjunct_type1000_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/pulse_cx.tmp");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+
+    let adjunct = read_adjunct(&file, &header).unwrap();
+    assert_eq!(adjunct.type_family(), 1000);
+
+    match adjunct {
+        Adjunct::Type1000(adj) => assert_eq!(adj.xunits, 1),
+        _ => panic!("Expected a type 1000 adjunct"),
+    }
+}
+
+#[test]
+fn read_adjunct_type3000_test() {
+    let mut v = header_bytes(&header(3000, b"NH"));
+    v[256..264].copy_from_slice(&10.0_f64.to_le_bytes());
+    v[264..272].copy_from_slice(&0.5_f64.to_le_bytes());
+    v[272..276].copy_from_slice(&1_i32.to_le_bytes());
+    v[276..280].copy_from_slice(&2_i32.to_le_bytes());
+    v[300..304].copy_from_slice(&16_i32.to_le_bytes());
+
+    let mut cursor = Cursor::new(v);
+    let header = read_header(&mut cursor).unwrap();
+
+    match read_adjunct(&mut cursor, &header).unwrap() {
+        Adjunct::Type3000(adj) => {
+            assert_eq!(adj.rstart, 10.0);
+            assert_eq!(adj.rdelta, 0.5);
+            assert_eq!(adj.runits, 1);
+            assert_eq!(adj.subrecords, 2);
+            assert_eq!(adj.record_length, 16);
+        },
+        _ => panic!("Expected a type 3000 adjunct"),
+    }
+}
+
+#[test]
+fn read_adjunct_type6000_test() {
+    let mut v = header_bytes(&header(6000, b"NH"));
+    v[304..308].copy_from_slice(&64_i32.to_le_bytes());
+
+    let mut cursor = Cursor::new(v);
+    let header = read_header(&mut cursor).unwrap();
+
+    match read_adjunct(&mut cursor, &header).unwrap() {
+        Adjunct::Type6000(adj) => assert_eq!(adj.subr_offset, 64),
+        _ => panic!("Expected a type 6000 adjunct"),
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use bluefile::{
+    Axis,
+    Bluefile,
+    DataType,
+    Error,
+    Header,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Units,
+};
+
+#[test]
+fn axis_values_test() {
+    let axis = Axis{start: 10.0, delta: 0.5, len: 4, units: Units::Time};
+    assert_eq!(axis.value(3), 11.5);
+    assert_eq!(axis.values(), vec![10.0, 10.5, 11.0, 11.5]);
+    assert_eq!(axis.iter().len(), 4);
+    assert_eq!((&axis).into_iter().skip(2).collect::<Vec<f64>>(), vec![11.0, 11.5]);
+}
+
+#[test]
+fn type1000_axis_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/pulse_cx.tmp");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+
+    let x_axis = bluefile.x_axis().unwrap();
+    assert_eq!(x_axis.len, 200);
+    assert_eq!(x_axis.units, Units::Time);
+    assert_eq!(x_axis.values()[199], 199.0);
+
+    match bluefile.y_axis() {
+        Err(Error::TypeCodeMismatchError) => {},
+        _ => panic!("A type 1000 file should not have a y axis"),
+    }
+}
+
+#[test]
+fn type2000_axis_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+
+    let x_axis = bluefile.x_axis().unwrap();
+    assert_eq!(x_axis, Axis{start: 0.0, delta: 1.0, len: 128, units: Units::None});
+
+    let y_axis = bluefile.y_axis().unwrap();
+    assert_eq!(y_axis, Axis{start: 0.0, delta: 1.0, len: 128, units: Units::None});
+    assert_eq!(y_axis.values().len(), bluefile.type2000_reader::<f64>().unwrap().num_frames());
+
+    let adjunct = Type2000Adjunct{
+        xstart: 0.0,
+        xdelta: 1.0,
+        xunits: 0,
+        subsize: 0,
+        ystart: 0.0,
+        ydelta: 1.0,
+        yunits: 0,
+    };
+    match adjunct.x_axis() {
+        Err(Error::InvalidSubsizeError(0)) => {},
+        _ => panic!("A zero subsize should be rejected"),
+    }
+}
+
+#[test]
+fn packed_axis_test() {
+    let header = Header{
+        type_code: 1000,
+        data_size: 6.0,
+        data_type: DataType{rank: b'S', format: b'P'},
+        ..Header::default()
+    };
+    let adjunct = Type1000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 0};
+    assert_eq!(adjunct.x_axis(&header).unwrap().len, 48);
+
+    // frames of 3 nibbles, 12 bits each
+    let header = Header{type_code: 2000, data_type: DataType{rank: b'S', format: b'N'}, ..header};
+    let adjunct = Type2000Adjunct{
+        xstart: 0.0,
+        xdelta: 1.0,
+        xunits: 0,
+        subsize: 3,
+        ystart: 0.0,
+        ydelta: 1.0,
+        yunits: 0,
+    };
+    assert_eq!(adjunct.y_axis(&header).unwrap().len, 4);
+
+    let header = Header{data_type: DataType{rank: b'S', format: b'?'}, ..header};
+    match adjunct.y_axis(&header) {
+        Err(Error::UnknownFormatError) => {},
+        _ => panic!("An unknown format should have produced an error"),
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs::read;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bluefile::{
+    Bluefile,
+    Error,
+};
+
+#[test]
+fn bluefile_type2000_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    assert_eq!(bluefile.header().type_code, 2000);
+
+    assert_eq!(bluefile.type2000_adjunct().unwrap().subsize, 128);
+    assert_eq!(bluefile.ext_header().unwrap().len(), 5);
+    assert_eq!(bluefile.ext_header().unwrap()[0].tag, "COMMENT".to_string());
+
+    match bluefile.type1000_adjunct() {
+        Err(Error::TypeCodeMismatchError) => {},
+        _ => panic!("Reading a type 1000 adjunct from a type 2000 file should have produced an error"),
+    }
+
+    let mut reader = bluefile.type2000_reader::<f64>().unwrap();
+    assert_eq!(reader.num_frames(), 128);
+    assert_eq!(reader.read_frame(64).unwrap()[60], 114.0);
+}
+
+#[test]
+fn bluefile_from_memory_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let mut bluefile = Bluefile::from_reader(Cursor::new(read(&d).unwrap())).unwrap();
+
+    assert_eq!(bluefile.type1000_adjunct().unwrap().xdelta, 1.0);
+    assert!(bluefile.ext_header().unwrap().is_empty());
+
+    let data: Vec<f64> = bluefile.read_type1000_data().unwrap();
+    assert_eq!(data.len(), 4096);
+    assert_eq!(data[0], 1.0);
+}
+
+#[test]
+fn bluefile_open_missing_test() {
+    match Bluefile::open("/does/not/exist.tmp") {
+        Err(Error::FileOpenError(_)) => {},
+        _ => panic!("Opening a missing file should have produced an error"),
+    }
+}
@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+use bluefile::{
+    DataType,
+    Endianness,
+    Header,
+    header_to_bytes,
+};
+
+/// Header of an attached little-endian file of the given type, with its data starting at byte 512.
+/// `format` is the two character data type, such as `SD`.
+pub fn header(type_code: i32, format: &[u8; 2]) -> Header {
+    Header{
+        type_code,
+        data_type: DataType{rank: format[0], format: format[1]},
+        ..Header::default()
+    }
+}
+
+/// Encodes a header followed by an adjunct of zeros, for the adjunct fields and data to be filled
+/// in after.
+pub fn header_bytes(header: &Header) -> Vec<u8> {
+    let mut v = header_to_bytes(header).unwrap();
+    v.resize(header.data_start as usize, 0);
+    v
+}
+
+/// Encodes a type 1000 file holding `data`, with the header in the given endianness.
+pub fn type1000_bytes(format: &[u8; 2], data: &[u8], endianness: Endianness) -> Vec<u8> {
+    let mut v = header_bytes(&Header{
+        header_endianness: endianness,
+        data_endianness: endianness,
+        data_size: data.len() as f64,
+        ..header(1000, format)
+    });
+    v.extend_from_slice(data);
+    v
+}
@@ -0,0 +1,109 @@
+use std::fs::File;
+use std::path::PathBuf;
+
+use num::complex::Complex;
+
+use bluefile::{
+    Error,
+    Header,
+    iter_type1000_data,
+    read_header,
+    read_type1000_data,
+    read_type2000_adjunct_header,
+    Type2000Reader,
+};
+
+#[test]
+fn read_type1000_data_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+
+    let data: Vec<f64> = read_type1000_data(&file, &header).unwrap();
+    assert_eq!(data.len(), 4096);
+    assert_eq!(data[0], 1.0);
+    assert_eq!(data[1], 0.9980267284282716);
+
+    let iter_data: Vec<f64> = iter_type1000_data(&file, &header).unwrap().map(|x| x.unwrap()).collect();
+    assert_eq!(iter_data, data);
+}
+
+#[test]
+fn read_type1000_complex_data_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/pulse_cx.tmp");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+
+    let data: Vec<Complex<f32>> = read_type1000_data(&file, &header).unwrap();
+    assert_eq!(data.len(), 200);
+    assert_eq!(data[0], Complex::new(0.0, 0.0));
+    assert_eq!(data[100], Complex::new(1.0, 1.0));
+}
+
+#[test]
+fn read_type1000_data_mismatch_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+
+    match read_type1000_data::<f32, _>(&file, &header) {
+        Err(Error::DataTypeMismatchError) => {},
+        _ => panic!("Reading SD data as f32 should have produced an error"),
+    }
+
+    d.pop();
+    d.push("penny.prm");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+
+    match read_type1000_data::<f64, _>(&file, &header) {
+        Err(Error::TypeCodeMismatchError) => {},
+        _ => panic!("Reading a type 2000 file as type 1000 should have produced an error"),
+    }
+}
+
+#[test]
+fn read_type1000_data_oversized_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let file = File::open(&d).unwrap();
+    let header = Header{data_size: 1e18, ..read_header(&file).unwrap()};
+
+    // a corrupt data size runs out of data rather than memory
+    match read_type1000_data::<f64, _>(&file, &header) {
+        Err(Error::FileReadError) => {},
+        _ => panic!("Reading past the end of the data should have produced an error"),
+    }
+}
+
+#[test]
+fn read_type2000_frames_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+    let adjunct = read_type2000_adjunct_header(&file, &header).unwrap();
+
+    let mut reader: Type2000Reader<_, f64> = Type2000Reader::new(&file, &header, &adjunct).unwrap();
+    assert_eq!(reader.subsize(), 128);
+    assert_eq!(reader.num_frames(), 128);
+
+    let frame = reader.read_frame(64).unwrap();
+    assert_eq!(frame.len(), 128);
+    assert_eq!(frame[60..64], [114.0, 116.0, 117.0, 118.0]);
+
+    let frames = reader.read_frames(63..66).unwrap();
+    assert_eq!(frames.len(), 3);
+    assert_eq!(frames[1], frame);
+
+    assert_eq!(reader.frames().count(), 128);
+    assert_eq!(reader.frames().nth(64).unwrap().unwrap(), frame);
+
+    match reader.read_frame(128) {
+        Err(Error::FrameOutOfRangeError(128)) => {},
+        _ => panic!("Reading past the last frame should have produced an error"),
+    }
+}
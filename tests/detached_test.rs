@@ -0,0 +1,213 @@
+use std::env::temp_dir;
+use std::fs::File;
+use std::fs::read;
+use std::fs::remove_file;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bluefile::{
+    Adjunct,
+    Bluefile,
+    DataType,
+    detached_data_path,
+    Endianness,
+    Error,
+    ExtKeyword,
+    ExtKeywordValue,
+    Header,
+    iter_packed_data,
+    iter_type1000_data,
+    open_data_file,
+    read_frame_time_range,
+    read_header,
+    read_packed_data,
+    read_time_range,
+    read_type1000_data,
+    read_type4000_keywords,
+    RecordReader,
+    Result,
+    Type2000Adjunct,
+    Type2000Reader,
+    Type3000Adjunct,
+    Type5000Adjunct,
+    Type6000Adjunct,
+    write_bluefile,
+    write_detached,
+};
+
+fn sin_parts() -> (Bluefile, Vec<u8>) {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let bluefile = Bluefile::open(&d).unwrap();
+    let start = bluefile.header().data_start as usize;
+    let end = start + bluefile.header().data_size as usize;
+    let data = read(&d).unwrap()[start..end].to_vec();
+    (bluefile, data)
+}
+
+#[test]
+fn detached_data_path_test() {
+    assert_eq!(detached_data_path("/data/recording.tmp"), PathBuf::from("/data/recording.det"));
+    assert_eq!(detached_data_path("recording"), PathBuf::from("recording.det"));
+}
+
+#[test]
+fn write_read_detached_test() {
+    let (mut sin, data) = sin_parts();
+    let header = sin.header().clone();
+    let adjunct = sin.adjunct().unwrap().clone();
+    let keywords = vec![ExtKeyword::new("SOURCE", ExtKeywordValue::from_string("sin.tmp", Endianness::Little))];
+
+    let header_path = temp_dir().join(format!("bluefile_detached_test_{}.tmp", std::process::id()));
+    let data_path = detached_data_path(&header_path);
+
+    let written = write_detached(
+        File::create(&header_path).unwrap(),
+        File::create(&data_path).unwrap(),
+        &header,
+        &adjunct,
+        &data,
+        &keywords,
+    ).unwrap();
+    assert!(written.is_detached());
+    assert_eq!(written.data_start, 0.0);
+
+    let mut bluefile = Bluefile::open(&header_path).unwrap();
+    assert!(bluefile.header().is_detached());
+    assert_eq!(bluefile.ext_header().unwrap(), &keywords[..]);
+
+    let samples: Vec<f64> = bluefile.read_type1000_data().unwrap();
+    assert_eq!(samples.len(), 4096);
+    assert_eq!(samples[1], 0.9980267284282716);
+
+    let file = File::open(&header_path).unwrap();
+    let header = read_header(&file).unwrap();
+    let samples: Vec<f64> = read_type1000_data(open_data_file(&header_path, &header).unwrap(), &header.attached()).unwrap();
+    assert_eq!(samples[0], 1.0);
+
+    // the header alone does not say where the data is
+    match read_type1000_data::<f64, _>(&file, &header) {
+        Err(Error::DetachedDataError) => {},
+        _ => panic!("Reading data with a detached header should have produced an error"),
+    }
+
+    // without the data file there is nothing to read the data from
+    let mut bluefile = Bluefile::from_reader(File::open(&header_path).unwrap()).unwrap();
+    match bluefile.read_type1000_data::<f64>() {
+        Err(Error::DetachedDataError) => {},
+        _ => panic!("Reading data without the detached data file should have produced an error"),
+    }
+
+    remove_file(&header_path).unwrap();
+    remove_file(&data_path).unwrap();
+}
+
+#[test]
+fn write_bluefile_test() {
+    let (mut sin, data) = sin_parts();
+    let header = sin.header().clone();
+    let adjunct = sin.adjunct().unwrap().clone();
+    let keywords = vec![ExtKeyword::new("GAIN", ExtKeywordValue::from_f64(0.5, Endianness::Little))];
+
+    let mut v = Vec::new();
+    let written = write_bluefile(&mut v, &header, &adjunct, &data, &keywords).unwrap();
+    assert_eq!(written.ext_start % 512, 0);
+    assert!(written.ext_start >= 512 + data.len());
+
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+    assert_eq!(bluefile.header(), &written);
+    match bluefile.adjunct().unwrap() {
+        Adjunct::Type1000(adj) => assert_eq!(adj.xdelta, 1.0),
+        _ => panic!("Expected a type 1000 adjunct"),
+    }
+    assert_eq!(bluefile.ext_header().unwrap(), &keywords[..]);
+
+    let samples: Vec<f64> = bluefile.read_type1000_data().unwrap();
+    assert_eq!(samples.len(), 4096);
+}
+
+#[test]
+fn write_mismatched_keyword_endianness_test() {
+    let (mut sin, data) = sin_parts();
+    let header = sin.header().clone();
+    let adjunct = sin.adjunct().unwrap().clone();
+    assert_eq!(header.header_endianness, Endianness::Little);
+
+    // keyword lengths are read back in the header endianness, so a big-endian value can't be stored
+    let keywords = vec![ExtKeyword::new("GAIN", ExtKeywordValue::from_f64(0.5, Endianness::Big))];
+
+    match write_bluefile(Vec::new(), &header, &adjunct, &data, &keywords) {
+        Err(Error::InvalidEndianness) => {},
+        _ => panic!("Writing a keyword with the wrong endianness should have produced an error"),
+    }
+
+    match write_detached(Vec::new(), Vec::new(), &header, &adjunct, &data, &keywords) {
+        Err(Error::InvalidEndianness) => {},
+        _ => panic!("Writing a keyword with the wrong endianness should have produced an error"),
+    }
+
+    // with matching keywords the file reads back
+    let keywords = vec![ExtKeyword::new("GAIN", ExtKeywordValue::from_f64(0.5, Endianness::Little))];
+    let mut v = Vec::new();
+    write_bluefile(&mut v, &header, &adjunct, &data, &keywords).unwrap();
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+    assert_eq!(bluefile.ext_header().unwrap(), &keywords[..]);
+}
+
+fn expect_detached_error<T>(result: Result<T>, name: &str) {
+    match result {
+        Err(Error::DetachedDataError) => {},
+        _ => panic!("{} should have rejected a detached header", name),
+    }
+}
+
+#[test]
+fn free_readers_reject_detached_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let bytes = read(&d).unwrap();
+    let file = || Cursor::new(bytes.clone());
+
+    let mut sin = Bluefile::open(&d).unwrap();
+    let type1000 = match sin.adjunct().unwrap() {
+        Adjunct::Type1000(adj) => adj.clone(),
+        _ => panic!("Expected a type 1000 adjunct"),
+    };
+    let header = Header{detached: 1, ..sin.header().clone()};
+    let typed = |type_code: i32| Header{type_code, ..header.clone()};
+
+    expect_detached_error(read_type1000_data::<f64, _>(file(), &header), "read_type1000_data");
+    expect_detached_error(iter_type1000_data::<f64, _>(file(), &header), "iter_type1000_data");
+    expect_detached_error(read_time_range::<f64, _>(file(), &header, &type1000, 0.0, 10.0), "read_time_range");
+
+    let packed = Header{data_type: DataType{rank: b'S', format: b'P'}, ..header.clone()};
+    expect_detached_error(read_packed_data(file(), &packed), "read_packed_data");
+    expect_detached_error(iter_packed_data(file(), &packed), "iter_packed_data");
+
+    let type2000 = Type2000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 0, subsize: 128, ystart: 0.0, ydelta: 1.0, yunits: 1};
+    expect_detached_error(Type2000Reader::<_, f64>::new(file(), &typed(2000), &type2000), "Type2000Reader::new");
+    expect_detached_error(read_frame_time_range::<f64, _>(file(), &typed(2000), &type2000, 0.0, 10.0), "read_frame_time_range");
+
+    let type3000 = Type3000Adjunct{
+        rstart: 0.0, rdelta: 1.0, runits: 0, subrecords: 0,
+        r2start: 0.0, r2delta: 1.0, r2units: 0, record_length: 8,
+        subrecord_table: vec![],
+    };
+    expect_detached_error(RecordReader::from_type3000(file(), &typed(3000), &type3000), "RecordReader::from_type3000");
+
+    expect_detached_error(read_type4000_keywords(file(), &typed(4000)), "read_type4000_keywords");
+
+    let type5000 = Type5000Adjunct{
+        rstart: 0.0, rdelta: 1.0, runits: 0, subrecords: 0,
+        r2start: 0.0, r2delta: 1.0, r2units: 0, record_length: 8,
+        components: vec![], coord_system: "ECR".to_string(),
+    };
+    expect_detached_error(RecordReader::from_type5000(file(), &typed(5000), &type5000), "RecordReader::from_type5000");
+
+    let type6000 = Type6000Adjunct{
+        rstart: 0.0, rdelta: 1.0, runits: 0, subrecords: 0,
+        r2start: 0.0, r2delta: 1.0, r2units: 0, record_length: 8,
+        subr_offset: 0,
+    };
+    expect_detached_error(RecordReader::from_type6000(file(), &typed(6000), &type6000), "RecordReader::from_type6000");
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use std::io::Cursor;
+
+use bluefile::{
+    DataType,
+    Endianness,
+    Error,
+    Matrix3,
+    Quaternion,
+    read_header,
+    read_type1000_data,
+    Value,
+    Vector,
+};
+
+use common::type1000_bytes;
+
+#[test]
+fn num_elements_test() {
+    let cases = [(b'S', 1), (b'C', 2), (b'V', 3), (b'Q', 4), (b'M', 9), (b'T', 16), (b'1', 1), (b'5', 5), (b'9', 9)];
+
+    for (rank, count) in cases {
+        let data_type = DataType{rank, format: b'F'};
+        assert_eq!(data_type.num_elements().unwrap(), count);
+        assert_eq!(data_type.element_size().unwrap(), 4);
+        assert_eq!(data_type.num_bytes().unwrap(), 4*count);
+    }
+
+    match (DataType{rank: b'0', format: b'F'}).num_bytes() {
+        Err(Error::UnknownRankError) => {},
+        _ => panic!("Rank 0 should have produced an error"),
+    }
+}
+
+#[test]
+fn read_vector_data_test() {
+    let data: Vec<u8> = (0..6).flat_map(|i| (i as f32).to_le_bytes()).collect();
+    let mut cursor = Cursor::new(type1000_bytes(b"VF", &data, Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    let vectors: Vec<Vector<f32>> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(vectors, vec![Vector([0.0, 1.0, 2.0]), Vector([3.0, 4.0, 5.0])]);
+
+    match read_type1000_data::<Quaternion<f32>, _>(&mut cursor, &header) {
+        Err(Error::DataTypeMismatchError) => {},
+        _ => panic!("Reading VF data as quaternions should have produced an error"),
+    }
+}
+
+#[test]
+fn read_matrix_data_test() {
+    let data: Vec<u8> = (0..9).flat_map(|i| (i as f64).to_le_bytes()).collect();
+    let mut cursor = Cursor::new(type1000_bytes(b"MD", &data, Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    let matrices: Vec<Matrix3<f64>> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(matrices, vec![Matrix3([[0.0, 1.0, 2.0], [3.0, 4.0, 5.0], [6.0, 7.0, 8.0]])]);
+}
+
+#[test]
+fn read_multichannel_data_test() {
+    let data: Vec<u8> = (0..6_i16).flat_map(|i| i.to_le_bytes()).collect();
+    let mut cursor = Cursor::new(type1000_bytes(b"3I", &data, Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    let samples: Vec<[i16; 3]> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(samples, vec![[0, 1, 2], [3, 4, 5]]);
+}
+
+#[test]
+fn decode_value_test() {
+    let data: Vec<u8> = (0..4_i32).flat_map(|i| i.to_le_bytes()).collect();
+
+    let value = Value::decode(&DataType{rank: b'Q', format: b'L'}, &data, Endianness::Little).unwrap();
+    assert_eq!(value, Value::Array(vec![Value::I32(0), Value::I32(1), Value::I32(2), Value::I32(3)]));
+    assert_eq!(value.to_string(), "[0, 1, 2, 3]".to_string());
+
+    let data: Vec<u8> = (0..16).flat_map(|i| (i as f32).to_le_bytes()).collect();
+    let value = Value::decode(&DataType{rank: b'T', format: b'F'}, &data, Endianness::Little).unwrap();
+    match value {
+        Value::Array(rows) => {
+            assert_eq!(rows.len(), 4);
+            assert_eq!(rows[1], Value::Array(vec![Value::F32(4.0), Value::F32(5.0), Value::F32(6.0), Value::F32(7.0)]));
+        },
+        _ => panic!("Expected an array of rows"),
+    }
+}
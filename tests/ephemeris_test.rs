@@ -0,0 +1,164 @@
+mod common;
+
+use std::io::Cursor;
+
+use bluefile::{
+    Adjunct,
+    adjunct_to_bytes,
+    Bluefile,
+    ComponentKind,
+    DataType,
+    Endianness,
+    Error,
+    Header,
+    read_state_vectors,
+    Type5000Adjunct,
+};
+
+use common::{
+    header,
+    header_bytes,
+};
+
+fn component(name: &[u8; 4], format: &[u8; 2], kind: u8) -> Vec<u8> {
+    let mut v = Vec::new();
+    v.extend_from_slice(name);
+    v.extend_from_slice(format);
+    v.push(kind);
+    v.push(0);
+    v
+}
+
+fn type5000_bytes(with_time: bool) -> Vec<u8> {
+    let mut comps = Vec::new();
+
+    if with_time {
+        comps.push(component(b"TIME", b"SD", 1));
+    }
+
+    for name in [b"POSX", b"POSY", b"POSZ"] {
+        comps.push(component(name, b"SD", 2));
+    }
+
+    for name in [b"VELX", b"VELY", b"VELZ"] {
+        comps.push(component(name, b"SF", 3));
+    }
+
+    let record_length: i32 = if with_time { 44 } else { 36 };
+
+    let mut v = header_bytes(&Header{data_size: (2 * record_length) as f64, ..header(5000, b"NH")});
+
+    v[256..264].copy_from_slice(&100.0_f64.to_le_bytes());
+    v[264..272].copy_from_slice(&10.0_f64.to_le_bytes());
+    v[276..280].copy_from_slice(&(comps.len() as i32).to_le_bytes());
+    v[300..304].copy_from_slice(&record_length.to_le_bytes());
+
+    for (i, c) in comps.iter().enumerate() {
+        v[304+8*i..312+8*i].copy_from_slice(c);
+    }
+
+    v[504..508].copy_from_slice(b"ECR ");
+
+    for i in 0..2 {
+        if with_time {
+            v.extend_from_slice(&(1000.0 + i as f64).to_le_bytes());
+        }
+
+        for p in [1.0_f64, 2.0, 3.0] {
+            v.extend_from_slice(&(p * (i + 1) as f64).to_le_bytes());
+        }
+
+        for p in [4.0_f32, 5.0, 6.0] {
+            v.extend_from_slice(&p.to_le_bytes());
+        }
+    }
+
+    v
+}
+
+#[test]
+fn read_type5000_adjunct_test() {
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type5000_bytes(false))).unwrap();
+
+    let adjunct = match bluefile.adjunct().unwrap() {
+        Adjunct::Type5000(adj) => adj.clone(),
+        _ => panic!("Expected a type 5000 adjunct"),
+    };
+
+    assert_eq!(adjunct.coord_system, "ECR".to_string());
+    assert_eq!(adjunct.components.len(), 6);
+    assert_eq!(adjunct.components[0].name, "POSX".to_string());
+    assert_eq!(adjunct.components[0].kind, ComponentKind::Position);
+    assert_eq!(adjunct.components[3].data_type, DataType{rank: b'S', format: b'F'});
+    assert_eq!(adjunct.components[3].kind, ComponentKind::Velocity);
+    assert_eq!(adjunct.components[5].name, "VELZ".to_string());
+
+    let subrecords = adjunct.subrecord_table().unwrap();
+    assert_eq!(subrecords[3].offset, 24);
+    assert_eq!(subrecords[5].offset, 32);
+
+    let mut reader = bluefile.record_reader().unwrap();
+    assert_eq!(reader.num_records(), 2);
+    assert_eq!(reader.read_record(1).unwrap().get("POSZ").unwrap().as_f64().unwrap(), 6.0);
+
+    let long_name = Adjunct::Type5000(Type5000Adjunct{coord_system: "GEODETIC".to_string(), ..adjunct});
+    match adjunct_to_bytes(&long_name, Endianness::Little) {
+        Err(Error::InvalidCoordSystemError(_)) => {},
+        _ => panic!("A coordinate system name longer than 4 bytes should have produced an error"),
+    }
+}
+
+#[test]
+fn read_state_vectors_test() {
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type5000_bytes(false))).unwrap();
+    let adjunct = match bluefile.adjunct().unwrap() {
+        Adjunct::Type5000(adj) => adj.clone(),
+        _ => panic!("Expected a type 5000 adjunct"),
+    };
+    let header = bluefile.header().clone();
+
+    let state_vectors = read_state_vectors(bluefile.into_inner(), &header, &adjunct).unwrap();
+    assert_eq!(state_vectors.len(), 2);
+    assert_eq!(state_vectors[1].time, 110.0);
+    assert_eq!(state_vectors[1].position, [2.0, 4.0, 6.0]);
+    assert_eq!(state_vectors[1].velocity, Some([4.0, 5.0, 6.0]));
+    assert_eq!(state_vectors[1].acceleration, None);
+
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type5000_bytes(true))).unwrap();
+    let adjunct = match bluefile.adjunct().unwrap() {
+        Adjunct::Type5000(adj) => adj.clone(),
+        _ => panic!("Expected a type 5000 adjunct"),
+    };
+    let header = bluefile.header().clone();
+
+    let state_vectors = read_state_vectors(bluefile.into_inner(), &header, &adjunct).unwrap();
+    assert_eq!(state_vectors[0].time, 1000.0);
+    assert_eq!(state_vectors[1].time, 1001.0);
+    assert_eq!(state_vectors[0].position, [1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn write_type5000_adjunct_test() {
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type5000_bytes(true))).unwrap();
+    let adjunct = match bluefile.adjunct().unwrap() {
+        Adjunct::Type5000(adj) => adj.clone(),
+        _ => panic!("Expected a type 5000 adjunct"),
+    };
+
+    // components are 8 bytes each from byte 48, and the frame is at byte 248
+    let v = adjunct_to_bytes(&Adjunct::Type5000(adjunct.clone()), Endianness::Little).unwrap();
+    assert_eq!(&v[56..64], b"POSXSD\x02\x00");
+    assert_eq!(&v[248..252], b"ECR ");
+
+    let mut components = adjunct.components.clone();
+    components.resize(14, adjunct.components[1].clone());
+    let full = Type5000Adjunct{components, ..adjunct.clone()};
+    assert!(adjunct_to_bytes(&Adjunct::Type5000(full.clone()), Endianness::Little).is_ok());
+
+    let mut components = full.components.clone();
+    components.push(adjunct.components[1].clone());
+    match adjunct_to_bytes(&Adjunct::Type5000(Type5000Adjunct{components, ..adjunct}), Endianness::Little) {
+        Err(Error::InvalidSubrecordCount(15)) => {},
+        _ => panic!("More than 14 components should have produced an error"),
+    }
+}
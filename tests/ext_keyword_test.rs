@@ -0,0 +1,136 @@
+use std::fs::File;
+use std::fs::read;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bluefile::{
+    Endianness,
+    Error,
+    ExtKeywordValue,
+    read_ext_header,
+    read_header,
+    Value,
+};
+
+#[test]
+fn ext_keyword_accessors_test() {
+    let value = ExtKeywordValue::from_f64(2.5, Endianness::Big);
+    assert_eq!(value.value().unwrap(), Value::F64(2.5));
+    assert_eq!(value.as_f64().unwrap(), 2.5);
+    assert_eq!(value.as_bytes(), &2.5_f64.to_be_bytes());
+
+    let value = ExtKeywordValue::from_i16(-7, Endianness::Little);
+    assert_eq!(value.as_i64().unwrap(), -7);
+    assert_eq!(value.as_f64().unwrap(), -7.0);
+
+    let value = ExtKeywordValue::from_string("north field", Endianness::Little);
+    assert_eq!(value.as_str().unwrap(), "north field");
+    assert_eq!(value.value().unwrap(), Value::String("north field".to_string()));
+
+    match value.as_f64() {
+        Err(Error::ValueConversionError) => {},
+        _ => panic!("A string should not convert to f64"),
+    }
+
+    match ExtKeywordValue::from_f32(1.5, Endianness::Little).as_i64() {
+        Err(Error::ValueConversionError) => {},
+        _ => panic!("A float should not convert to i64"),
+    }
+
+    match ExtKeywordValue::from_i32(1, Endianness::Little).as_str() {
+        Err(Error::ValueConversionError) => {},
+        _ => panic!("A number should not convert to a string"),
+    }
+}
+
+#[test]
+fn ext_keyword_malformed_test() {
+    let truncated = ExtKeywordValue{format: 'D', endianness: Endianness::Little, raw_value: vec![0, 1, 2]};
+    match truncated.as_f64() {
+        Err(Error::ByteConversionError) => {},
+        _ => panic!("A truncated value should not decode"),
+    }
+    assert_eq!(truncated.to_string(), "\"?\"");
+
+    let unknown = ExtKeywordValue{format: 'Q', endianness: Endianness::Little, raw_value: vec![0; 8]};
+    match unknown.value() {
+        Err(Error::UnknownFormatError) => {},
+        _ => panic!("An unknown format should not decode"),
+    }
+    assert_eq!(unknown.to_string(), "\"?\"");
+
+    let bad_utf8 = ExtKeywordValue{format: 'A', endianness: Endianness::Little, raw_value: vec![0xff, 0xfe]};
+    assert_eq!(bad_utf8.to_string(), "\"?\"");
+}
+
+#[test]
+fn ext_keyword_file_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+    let keywords = read_ext_header(&file, &header).unwrap();
+    assert_eq!(keywords[0].value.as_str().unwrap(), "Demo data for XRTSURFACE/STAY");
+}
+
+#[test]
+fn ext_keyword_corrupt_file_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let bytes = read(&d).unwrap();
+    let header = read_header(Cursor::new(&bytes)).unwrap();
+    let start = header.ext_start;
+    let big = header.header_endianness == Endianness::Big;
+
+    // block shorter than its own keyword header
+    let mut v = bytes.clone();
+    v[start..start+4].copy_from_slice(&if big { 2_i32.to_be_bytes() } else { 2_i32.to_le_bytes() });
+    match read_ext_header(Cursor::new(v), &header) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A keyword block shorter than its header should have produced an error"),
+    }
+
+    // keyword header longer than the block
+    let mut v = bytes.clone();
+    v[start+4..start+6].copy_from_slice(&if big { i16::MAX.to_be_bytes() } else { i16::MAX.to_le_bytes() });
+    match read_ext_header(Cursor::new(v), &header) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A keyword header longer than its block should have produced an error"),
+    }
+
+    // block longer than the extended header
+    let mut v = bytes.clone();
+    v[start..start+4].copy_from_slice(&if big { i32::MAX.to_be_bytes() } else { i32::MAX.to_le_bytes() });
+    match read_ext_header(Cursor::new(v), &header) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A keyword block longer than the extended header should have produced an error"),
+    }
+}
+
+#[test]
+fn ext_keyword_array_test() {
+    let raw_value: Vec<u8> = [1.0_f64, 2.5, -3.0].iter().flat_map(|x| x.to_le_bytes()).collect();
+    let value = ExtKeywordValue{format: 'D', endianness: Endianness::Little, raw_value};
+    assert_eq!(value.num_elements().unwrap(), 3);
+    assert_eq!(value.as_f64_vec().unwrap(), vec![1.0, 2.5, -3.0]);
+    assert_eq!(value.value().unwrap(), Value::Array(vec![Value::F64(1.0), Value::F64(2.5), Value::F64(-3.0)]));
+    assert_eq!(value.to_string(), "[1, 2.5, -3]");
+
+    match value.as_f64() {
+        Err(Error::ValueConversionError) => {},
+        _ => panic!("An array should not convert to a single f64"),
+    }
+
+    let raw_value: Vec<u8> = [7_i32, -8].iter().flat_map(|x| x.to_be_bytes()).collect();
+    let value = ExtKeywordValue{format: 'L', endianness: Endianness::Big, raw_value};
+    assert_eq!(value.as_i64_vec().unwrap(), vec![7, -8]);
+
+    let single = ExtKeywordValue::from_i32(4, Endianness::Little);
+    assert_eq!(single.as_i64_vec().unwrap(), vec![4]);
+
+    let ragged = ExtKeywordValue{format: 'L', endianness: Endianness::Little, raw_value: vec![0; 6]};
+    match ragged.values() {
+        Err(Error::ByteConversionError) => {},
+        _ => panic!("A value that is not a whole number of elements should not decode"),
+    }
+}
@@ -0,0 +1,164 @@
+mod common;
+
+use std::env::temp_dir;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::remove_file;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bluefile::{
+    Adjunct,
+    Bluefile,
+    Endianness,
+    Error,
+    FollowReader,
+    Header,
+    header_to_bytes,
+    read_header,
+    Type2000Adjunct,
+    write_adjunct,
+};
+
+use common::header;
+
+fn create_recording(name: &str, header: &Header, subsize: i32) -> PathBuf {
+    let path = temp_dir().join(format!("bluefile_{}_{}.tmp", name, std::process::id()));
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&header_to_bytes(header).unwrap()).unwrap();
+
+    let adjunct = Adjunct::Type2000(Type2000Adjunct{
+        xstart: 0.0,
+        xdelta: 1.0,
+        xunits: 0,
+        subsize,
+        ystart: 0.0,
+        ydelta: 1.0,
+        yunits: 0,
+    });
+    write_adjunct(&mut file, &adjunct, Endianness::Little).unwrap();
+    path
+}
+
+/// Appends samples the way a recorder would, then updates data_size in the header.
+fn record(path: &Path, header: &mut Header, samples: &[i16]) {
+    let mut file = OpenOptions::new().write(true).open(path).unwrap();
+    file.seek(SeekFrom::End(0)).unwrap();
+
+    for x in samples {
+        file.write_all(&x.to_le_bytes()).unwrap();
+    }
+
+    header.data_size += (samples.len() * 2) as f64;
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.write_all(&header_to_bytes(header).unwrap()).unwrap();
+}
+
+#[test]
+fn follow_type1000_test() {
+    let mut header = header(1000, b"SI");
+    let path = create_recording("follow_type1000_test", &header, 0);
+
+    let file = File::open(&path).unwrap();
+    let mut follower: FollowReader<&File, i16> = FollowReader::new(&file, &read_header(&file).unwrap()).unwrap();
+    follower.set_poll_interval(Duration::from_millis(1));
+    assert_eq!(follower.read_new().unwrap(), vec![]);
+
+    record(&path, &mut header, &[1, 2, 3]);
+    assert_eq!(follower.wait_new(Some(Duration::from_millis(100))).unwrap(), vec![1, 2, 3]);
+
+    match follower.wait_new(Some(Duration::from_millis(10))) {
+        Err(Error::FollowTimeoutError) => {},
+        _ => panic!("Waiting with nothing recorded should have timed out"),
+    }
+
+    // a header in the middle of being rewritten is skipped
+    let mut writer = OpenOptions::new().write(true).open(&path).unwrap();
+    writer.write_all(&[0_u8; 16]).unwrap();
+    assert_eq!(follower.read_new().unwrap(), vec![]);
+
+    record(&path, &mut header, &[4, 5]);
+    assert_eq!(follower.read_new().unwrap(), vec![4, 5]);
+    assert_eq!(follower.samples_read(), 5);
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn follow_type2000_test() {
+    let mut header = header(2000, b"SI");
+    let path = create_recording("follow_type2000_test", &header, 2);
+
+    let mut bluefile = Bluefile::open(&path).unwrap();
+    let mut follower = bluefile.follow_reader::<i16>().unwrap();
+    follower.set_poll_interval(Duration::from_millis(1));
+    follower.set_idle_timeout(Some(Duration::from_millis(20)));
+    assert_eq!(follower.frame_size(), 2);
+
+    // only whole frames are handed out
+    record(&path, &mut header, &[1, 2, 3]);
+    assert_eq!(follower.read_new().unwrap(), vec![1, 2]);
+
+    record(&path, &mut header, &[4, 5, 6]);
+    let frames: Vec<Vec<i16>> = follower.frames().map(|x| x.unwrap()).collect();
+    assert_eq!(frames, vec![vec![3, 4], vec![5, 6]]);
+
+    // large backlogs are handed out a batch at a time
+    follower.set_max_batch_bytes(9);
+    record(&path, &mut header, &[7, 8, 9, 10, 11, 12]);
+    assert_eq!(follower.read_new().unwrap(), vec![7, 8, 9, 10]);
+    follower.set_max_batch_bytes(0);
+    assert_eq!(follower.read_new().unwrap(), vec![11, 12]);
+    assert_eq!(follower.read_new().unwrap(), vec![]);
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn follow_detached_test() {
+    let header = header(1000, b"SI");
+    let path = create_recording("follow_detached_test", &header, 0);
+
+    // the data of a detached header is not in the file being followed
+    let file = File::open(&path).unwrap();
+    let detached = Header{detached: 1, ..read_header(&file).unwrap()};
+    match FollowReader::<&File, i16>::new(&file, &detached) {
+        Err(Error::DetachedDataError) => {},
+        _ => panic!("Following a detached header should have produced an error"),
+    }
+
+    remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "notify")]
+#[test]
+fn follow_watch_test() {
+    use std::thread;
+    use std::time::Instant;
+
+    let mut header = header(1000, b"SI");
+    let path = create_recording("follow_watch_test", &header, 0);
+
+    let file = File::open(&path).unwrap();
+    let mut follower: FollowReader<&File, i16> = FollowReader::new(&file, &read_header(&file).unwrap()).unwrap();
+    follower.set_poll_interval(Duration::from_secs(5));
+    follower.watch(&path).unwrap();
+
+    let recorder_path = path.clone();
+    let recorder = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        record(&recorder_path, &mut header, &[7, 8]);
+    });
+
+    // woken by the change notification rather than the slow poll
+    let start = Instant::now();
+    assert_eq!(follower.wait_new(Some(Duration::from_secs(10))).unwrap(), vec![7, 8]);
+    assert!(start.elapsed() < Duration::from_secs(4));
+
+    recorder.join().unwrap();
+    remove_file(&path).unwrap();
+}
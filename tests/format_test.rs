@@ -0,0 +1,62 @@
+mod common;
+
+use std::io::Cursor;
+
+use num::complex::Complex;
+
+use bluefile::{
+    DataType,
+    Endianness,
+    ExtKeywordValue,
+    read_header,
+    read_type1000_data,
+    Value,
+};
+
+use common::type1000_bytes;
+
+#[test]
+fn read_unsigned_data_test() {
+    let mut cursor = Cursor::new(type1000_bytes(b"SO", &[0, 128, 255], Endianness::Big));
+    let header = read_header(&mut cursor).unwrap();
+    let data: Vec<u8> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data, vec![0, 128, 255]);
+
+    let mut cursor = Cursor::new(type1000_bytes(b"SU", &[0xff, 0xfe, 0x00, 0x01], Endianness::Big));
+    let header = read_header(&mut cursor).unwrap();
+    let data: Vec<u16> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data, vec![65534, 1]);
+
+    let mut cursor = Cursor::new(type1000_bytes(b"SV", &4000000000_u32.to_be_bytes(), Endianness::Big));
+    let header = read_header(&mut cursor).unwrap();
+    let data: Vec<u32> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data, vec![4000000000]);
+
+    let mut cursor = Cursor::new(type1000_bytes(b"CU", &[0x80, 0x00, 0x00, 0x10], Endianness::Big));
+    let header = read_header(&mut cursor).unwrap();
+    let data: Vec<Complex<u16>> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data, vec![Complex::new(32768, 16)]);
+}
+
+#[test]
+fn read_ascii_data_test() {
+    let mut cursor = Cursor::new(type1000_bytes(b"SA", b"hello", Endianness::Big));
+    let header = read_header(&mut cursor).unwrap();
+    assert_eq!(header.data_type.num_bytes().unwrap(), 1);
+
+    let data: Vec<char> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data.iter().collect::<String>(), "hello".to_string());
+}
+
+#[test]
+fn decode_unsigned_value_test() {
+    let value = Value::decode(&DataType{rank: b'S', format: b'U'}, &[0xff, 0xff], Endianness::Big).unwrap();
+    assert_eq!(value, Value::U16(65535));
+    assert_eq!(value.as_i64().unwrap(), 65535);
+
+    let value = Value::decode(&DataType{rank: b'V', format: b'O'}, &[1, 2, 3], Endianness::Big).unwrap();
+    assert_eq!(value.to_string(), "[1, 2, 3]".to_string());
+
+    let value = ExtKeywordValue{format: 'V', endianness: Endianness::Little, raw_value: 3000000000_u32.to_le_bytes().to_vec()};
+    assert_eq!(value.to_string(), "3000000000".to_string());
+}
@@ -1,11 +1,19 @@
 use std::fs::File;
+use std::fs::read;
+use std::io::Cursor;
 use std::path::PathBuf;
 
 use bluefile::{
     DataType,
     Endianness,
+    Error,
     Header,
+    header_to_bytes,
+    HeaderKeyword,
+    parse_header,
+    read_ext_header,
     read_header,
+    read_type2000_adjunct_header,
 };
 
 #[test]
@@ -18,14 +26,124 @@ fn read_bad_header_test() {
         Err(_) => Header{
             header_endianness: Endianness::Little,
             data_endianness: Endianness::Little,
+            detached: 0,
+            protected: 0,
+            pipe: 0,
             ext_start: 0,
             ext_size: 0,
             data_start: 0.0,
             data_size: 0.0,
             type_code: 1000,
             data_type: DataType{format: 0, rank: 0},
+            flagmask: 0,
             timecode: 0.0,
+            inlet: 0,
+            outlets: 0,
+            outmask: 0,
+            pipeloc: 0,
+            pipesize: 0,
+            in_byte: 0.0,
+            out_byte: 0.0,
+            outbytes: [0.0; 8],
+            keylength: 0,
             keywords: vec![],
         },
     };
 }
+
+#[test]
+fn read_header_from_memory_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let mut cursor = Cursor::new(read(&d).unwrap());
+
+    let header = read_header(&mut cursor).unwrap();
+    assert_eq!(header.type_code, 2000);
+    assert_eq!(header.data_type, DataType{rank: b'S', format: b'D'});
+
+    let adjunct = read_type2000_adjunct_header(&mut cursor, &header).unwrap();
+    assert_eq!(adjunct.subsize, 128);
+
+    let ext_keywords = read_ext_header(&mut cursor, &header).unwrap();
+    assert_eq!(ext_keywords.len(), 5);
+}
+
+#[test]
+fn read_header_fields_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/lots_of_keywords.tmp");
+    let file = File::open(&d).unwrap();
+    let header = read_header(&file).unwrap();
+    assert_eq!(header.detached, 0);
+    assert_eq!(header.protected, 1);
+    assert_eq!(header.pipe, 0);
+}
+
+#[test]
+fn header_round_trip_test() {
+    let mut v = vec![0_u8; 256];
+    v[0..4].copy_from_slice(b"BLUE");
+    v[4..8].copy_from_slice(b"IEEE");
+    v[8..12].copy_from_slice(b"EEEI");
+    v[12..16].copy_from_slice(&1_i32.to_be_bytes());
+    v[16..20].copy_from_slice(&1_i32.to_be_bytes());
+    v[20..24].copy_from_slice(&1_i32.to_be_bytes());
+    v[24..28].copy_from_slice(&2_i32.to_be_bytes());
+    v[28..32].copy_from_slice(&64_i32.to_be_bytes());
+    v[32..40].copy_from_slice(&512.0_f64.to_be_bytes());
+    v[40..48].copy_from_slice(&4096.0_f64.to_be_bytes());
+    v[48..52].copy_from_slice(&1000_i32.to_be_bytes());
+    v[52..54].copy_from_slice(b"SF");
+    v[54..56].copy_from_slice(&3_i16.to_be_bytes());
+    v[56..64].copy_from_slice(&1.5e9_f64.to_be_bytes());
+    v[64..66].copy_from_slice(&1_i16.to_be_bytes());
+    v[66..68].copy_from_slice(&2_i16.to_be_bytes());
+    v[68..72].copy_from_slice(&5_i32.to_be_bytes());
+    v[72..76].copy_from_slice(&7_i32.to_be_bytes());
+    v[76..80].copy_from_slice(&65536_i32.to_be_bytes());
+    v[80..88].copy_from_slice(&1024.0_f64.to_be_bytes());
+    v[88..96].copy_from_slice(&512.0_f64.to_be_bytes());
+    for i in 0..8 {
+        v[96+i*8..104+i*8].copy_from_slice(&(256.0*(i + 1) as f64).to_be_bytes());
+    }
+    v[160..164].copy_from_slice(&8_i32.to_be_bytes());
+    v[164..172].copy_from_slice(b"IO=TEST\0");
+
+    let header = parse_header(&v).unwrap();
+    assert_eq!(header.detached, 1);
+    assert_eq!(header.protected, 1);
+    assert_eq!(header.pipe, 1);
+    assert_eq!(header.ext_start, 1024);
+    assert_eq!(header.ext_size, 64);
+    assert_eq!(header.data_size, 4096.0);
+    assert_eq!(header.flagmask, 3);
+    assert_eq!(header.timecode, 1.5e9);
+    assert_eq!(header.inlet, 1);
+    assert_eq!(header.outlets, 2);
+    assert_eq!(header.outmask, 5);
+    assert_eq!(header.pipeloc, 7);
+    assert_eq!(header.pipesize, 65536);
+    assert_eq!(header.in_byte, 1024.0);
+    assert_eq!(header.out_byte, 512.0);
+    assert_eq!(header.outbytes[1], 512.0);
+    assert_eq!(header.outbytes[7], 2048.0);
+    assert_eq!(header.keylength, 8);
+    assert_eq!(header.keywords[0], HeaderKeyword{name: "IO".to_string(), value: "TEST".to_string()});
+
+    assert_eq!(header_to_bytes(&header).unwrap(), v);
+
+    let mut mismatched = header.clone();
+    mismatched.keylength = 12;
+    match header_to_bytes(&mismatched) {
+        Err(Error::HeaderKeywordLengthMismatchError(12, 8)) => {},
+        _ => panic!("A keylength that does not match the keywords should have produced an error"),
+    }
+}
+
+#[test]
+fn default_header_test() {
+    let header = Header::default();
+    assert!(!header.is_detached());
+    assert_eq!(header.data_start, 512.0);
+    assert_eq!(parse_header(&header_to_bytes(&header).unwrap()).unwrap(), header);
+}
@@ -0,0 +1,88 @@
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use bluefile::{
+    Bluefile,
+    Endianness,
+    ExtKeyword,
+    ExtKeywordValue,
+    KeywordTree,
+    write_bluefile,
+};
+
+fn keyword_f64(tag: &str, v: f64) -> ExtKeyword {
+    ExtKeyword::new(tag, ExtKeywordValue::from_f64(v, Endianness::Little))
+}
+
+fn grouped_keywords() -> Vec<ExtKeyword> {
+    vec![
+        keyword_f64("RF.FREQ", 1.5e9),
+        keyword_f64("GEO.LAT", 38.9),
+        keyword_f64("RF.BW", 2e6),
+        keyword_f64("GEO.LON", -77.0),
+        keyword_f64("RF.FREQ", 1.6e9),
+        ExtKeyword::new("COMMENT", ExtKeywordValue::from_string("grouped", Endianness::Little)),
+        keyword_f64("RF.IF.GAIN", 20.0),
+    ]
+}
+
+#[test]
+fn keyword_tree_lookup_test() {
+    let tree = KeywordTree::from_keywords(&grouped_keywords());
+
+    assert_eq!(tree.get("RF.BW").unwrap().as_f64().unwrap(), 2e6);
+    assert_eq!(tree.get("RF.IF.GAIN").unwrap().as_f64().unwrap(), 20.0);
+    assert_eq!(tree.get("COMMENT").unwrap().as_str().unwrap(), "grouped");
+    assert!(tree.get("RF").is_none());
+    assert!(tree.get("RF.PHASE").is_none());
+
+    // duplicates keep file order, and get returns the first
+    assert_eq!(tree.get("RF.FREQ").unwrap().as_f64().unwrap(), 1.5e9);
+    let all: Vec<f64> = tree.get_all("RF.FREQ").iter().map(|v| v.as_f64().unwrap()).collect();
+    assert_eq!(all, vec![1.5e9, 1.6e9]);
+
+    let names: Vec<&str> = tree.children().iter().map(|c| c.name()).collect();
+    assert_eq!(names, vec!["RF", "GEO", "COMMENT"]);
+
+    let rf = tree.subtree("RF").unwrap();
+    let tags: Vec<String> = rf.entries().into_iter().map(|(tag, _)| tag).collect();
+    assert_eq!(tags, vec!["FREQ", "FREQ", "BW", "IF.GAIN"]);
+    assert_eq!(rf.subtree("IF").unwrap().get("GAIN").unwrap().as_f64().unwrap(), 20.0);
+}
+
+#[test]
+fn keyword_tree_round_trip_test() {
+    let tree = KeywordTree::from_keywords(&grouped_keywords());
+    let flat = tree.to_keywords();
+    let tags: Vec<&str> = flat.iter().map(|k| k.tag.as_str()).collect();
+    assert_eq!(tags, vec!["RF.FREQ", "RF.FREQ", "RF.BW", "RF.IF.GAIN", "GEO.LAT", "GEO.LON", "COMMENT"]);
+
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let mut sin = Bluefile::open(&d).unwrap();
+    let header = sin.header().clone();
+    let adjunct = sin.adjunct().unwrap().clone();
+
+    let mut buf = Cursor::new(vec![]);
+    write_bluefile(&mut buf, &header, &adjunct, &[0_u8; 8], &flat).unwrap();
+    buf.set_position(0);
+    assert_eq!(Bluefile::from_reader(buf).unwrap().keyword_tree().unwrap(), tree);
+
+    let mut built = KeywordTree::new();
+    built.insert("A.B", ExtKeywordValue::from_i32(1, Endianness::Little));
+    built.insert("A", ExtKeywordValue::from_i32(2, Endianness::Little));
+    let tags: Vec<String> = built.to_keywords().into_iter().map(|k| k.tag).collect();
+    assert_eq!(tags, vec!["A", "A.B"]);
+}
+
+#[test]
+fn keyword_tree_file_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    let tree = bluefile.keyword_tree().unwrap();
+    assert_eq!(tree.children().len(), 4);
+    assert_eq!(tree.get_all("COMMENT").len(), 2);
+    assert_eq!(tree.get("COMMENT").unwrap().as_str().unwrap(), "Demo data for XRTSURFACE/STAY");
+    assert_eq!(tree.to_keywords(), bluefile.ext_header().unwrap().to_vec());
+}
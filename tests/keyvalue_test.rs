@@ -0,0 +1,119 @@
+use std::io::Cursor;
+
+use bluefile::{
+    Adjunct,
+    Bluefile,
+    bytes_to_f64,
+    bytes_to_i32,
+    Endianness,
+    Error,
+    ExtKeyword,
+    ExtKeywordValue,
+    Header,
+    read_header,
+    read_type4000_keywords,
+    write_type4000,
+};
+
+fn keywords(endianness: Endianness) -> Vec<ExtKeyword> {
+    vec![
+        ExtKeyword::new("GAIN", ExtKeywordValue::from_f64(2.5, endianness)),
+        ExtKeyword::new("CHANNEL", ExtKeywordValue::from_i32(7, endianness)),
+        ExtKeyword::new("SITE", ExtKeywordValue::from_string("north field", endianness)),
+    ]
+}
+
+#[test]
+fn write_read_type4000_test() {
+    for endianness in [Endianness::Little, Endianness::Big] {
+        let mut v = Vec::new();
+        write_type4000(&mut v, &keywords(endianness), endianness).unwrap();
+
+        let mut cursor = Cursor::new(v);
+        let header = read_header(&mut cursor).unwrap();
+        assert_eq!(header.type_code, 4000);
+        assert_eq!(header.data_endianness, endianness);
+
+        let read = read_type4000_keywords(&mut cursor, &header).unwrap();
+        assert_eq!(read, keywords(endianness));
+        assert_eq!(bytes_to_f64(&read[0].value.raw_value, endianness).unwrap(), 2.5);
+        assert_eq!(bytes_to_i32(&read[1].value.raw_value, endianness).unwrap(), 7);
+        assert_eq!(read[2].value.to_string(), "\"north field\"".to_string());
+    }
+}
+
+#[test]
+fn bluefile_type4000_test() {
+    let mut v = Vec::new();
+    write_type4000(&mut v, &keywords(Endianness::Little), Endianness::Little).unwrap();
+
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+
+    match bluefile.adjunct().unwrap() {
+        Adjunct::Type4000(adj) => assert_eq!(adj.nrecords, 1),
+        _ => panic!("Expected a type 4000 adjunct"),
+    }
+
+    let read = bluefile.read_type4000_keywords().unwrap();
+    assert_eq!(read.len(), 3);
+    assert_eq!(read[0].tag, "GAIN".to_string());
+    assert_eq!(read[2].tag, "SITE".to_string());
+}
+
+#[test]
+fn write_type4000_mixed_endianness_test() {
+    let mut keywords = keywords(Endianness::Little);
+    keywords.push(ExtKeyword::new("BAD", ExtKeywordValue::from_i16(1, Endianness::Big)));
+
+    let mut v = Vec::new();
+    match write_type4000(&mut v, &keywords, Endianness::Little) {
+        Err(Error::InvalidEndianness) => {},
+        _ => panic!("Writing values of mixed endianness should have produced an error"),
+    }
+}
+
+/// Writes a type 4000 file and lets `corrupt` modify the first keyword block, which holds GAIN.
+fn read_corrupted_type4000(corrupt: fn(&mut [u8])) -> bluefile::Result<Vec<ExtKeyword>> {
+    let mut v = Vec::new();
+    write_type4000(&mut v, &keywords(Endianness::Little), Endianness::Little).unwrap();
+    corrupt(&mut v[512..]);
+
+    let mut cursor = Cursor::new(v);
+    let header = read_header(&mut cursor).unwrap();
+    read_type4000_keywords(&mut cursor, &header)
+}
+
+#[test]
+fn read_type4000_malformed_block_test() {
+    // lext longer than the whole block
+    match read_corrupted_type4000(|b| b[4..6].copy_from_slice(&100_i16.to_le_bytes())) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A keyword header longer than its block should have produced an error"),
+    }
+
+    // tag running past the end of the block
+    match read_corrupted_type4000(|b| b[6] = 255) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A tag past the end of its block should have produced an error"),
+    }
+
+    // tag that is not UTF-8
+    match read_corrupted_type4000(|b| b[16..20].copy_from_slice(&[0xff, 0xfe, 0xff, 0xfe])) {
+        Err(Error::ExtHeaderKeywordTagParseError) => {},
+        _ => panic!("A tag that is not UTF-8 should have produced an error"),
+    }
+}
+
+#[test]
+fn read_type4000_oversized_test() {
+    let mut v = Vec::new();
+    write_type4000(&mut v, &keywords(Endianness::Little), Endianness::Little).unwrap();
+
+    // a corrupt data size runs out of data rather than memory
+    let mut cursor = Cursor::new(v);
+    let header = Header{data_size: 1e18, ..read_header(&mut cursor).unwrap()};
+    match read_type4000_keywords(&mut cursor, &header) {
+        Err(Error::FileReadError) => {},
+        _ => panic!("Reading past the end of the keywords should have produced an error"),
+    }
+}
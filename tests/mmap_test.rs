@@ -0,0 +1,83 @@
+#![cfg(feature = "mmap")]
+
+use std::borrow::Cow;
+use std::env::temp_dir;
+use std::fs::File;
+use std::fs::remove_file;
+use std::fs::write;
+use std::path::PathBuf;
+
+use num::complex::Complex;
+
+use bluefile::{
+    Adjunct,
+    adjunct_to_bytes,
+    Endianness,
+    Error,
+    Header,
+    header_to_bytes,
+    MappedBluefile,
+    read_type2000_adjunct_header,
+    Type1000Adjunct,
+};
+
+#[test]
+fn mapped_type1000_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let bluefile = MappedBluefile::open(&d).unwrap();
+    assert_eq!(bluefile.header().type_code, 1000);
+
+    let data = bluefile.samples::<f64>().unwrap();
+    assert!(matches!(data, Cow::Borrowed(_)) || cfg!(target_endian = "big"));
+    assert_eq!(data.len(), 4096);
+    assert_eq!(data[1], 0.9980267284282716);
+
+    let data = bluefile.samples_range::<f64>(1..3).unwrap();
+    assert_eq!(data.len(), 2);
+    assert_eq!(data[0], 0.9980267284282716);
+    assert!(bluefile.samples_range::<f64>(4000..4097).is_err());
+    assert!(bluefile.samples::<f32>().is_err());
+
+    d.pop();
+    d.push("pulse_cx.tmp");
+    let bluefile = MappedBluefile::open(&d).unwrap();
+    let data = bluefile.samples::<Complex<f32>>().unwrap();
+    assert_eq!(data.len(), 200);
+    assert_eq!(data[100], Complex::new(1.0, 1.0));
+}
+
+#[test]
+fn mapped_type2000_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let bluefile = MappedBluefile::open(&d).unwrap();
+    let file = File::open(&d).unwrap();
+    let adjunct = read_type2000_adjunct_header(&file, bluefile.header()).unwrap();
+
+    let frame = bluefile.frame::<f64>(&adjunct, 64).unwrap();
+    assert_eq!(frame.len(), 128);
+    assert_eq!(frame[60..64], [114.0, 116.0, 117.0, 118.0]);
+    assert!(bluefile.frame::<f64>(&adjunct, 128).is_err());
+}
+
+#[test]
+fn mapped_oversized_data_test() {
+    // a data_size this large would overflow data_start + data_size
+    let header = Header{data_size: 1.0e20, ..Header::default()};
+    let adjunct = Adjunct::Type1000(Type1000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 0});
+    let mut v = header_to_bytes(&header).unwrap();
+    v.extend(adjunct_to_bytes(&adjunct, Endianness::Little).unwrap());
+    v.extend([0_u8; 64]);
+
+    let path = temp_dir().join(format!("bluefile_mapped_oversized_data_test_{}.tmp", std::process::id()));
+    write(&path, &v).unwrap();
+
+    let bluefile = MappedBluefile::open(&path).unwrap();
+    match bluefile.data_bytes() {
+        Err(Error::NotEnoughDataBytes(64)) => {},
+        _ => panic!("A data section past the end of the file should have produced an error"),
+    }
+
+    remove_file(&path).unwrap();
+}
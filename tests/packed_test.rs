@@ -0,0 +1,72 @@
+mod common;
+
+use std::io::Cursor;
+
+use bluefile::{
+    DataType,
+    Endianness,
+    Error,
+    iter_packed_data,
+    read_header,
+    read_packed_data,
+    read_type1000_data,
+};
+
+use common::type1000_bytes;
+
+#[test]
+fn packed_bits_size_test() {
+    let bits = DataType{rank: b'S', format: b'P'};
+    assert_eq!(bits.num_bits().unwrap(), 1);
+    assert!(bits.is_packed());
+
+    match bits.num_bytes() {
+        Err(Error::PackedFormatError) => {},
+        _ => panic!("Packed bits should not have a byte size"),
+    }
+
+    assert_eq!((DataType{rank: b'C', format: b'N'}).num_bits().unwrap(), 8);
+    assert_eq!((DataType{rank: b'S', format: b'D'}).num_bits().unwrap(), 64);
+}
+
+#[test]
+fn read_packed_bits_test() {
+    let mut cursor = Cursor::new(type1000_bytes(b"SP", &[0b1010_0001, 0b1100_0000], Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    let bits = read_packed_data(&mut cursor, &header).unwrap();
+    assert_eq!(bits.len(), 16);
+    assert_eq!(bits[0..8], [1, 0, 1, 0, 0, 0, 0, 1]);
+    assert_eq!(bits[8..10], [1, 1]);
+
+    let first: Vec<u8> = iter_packed_data(&mut cursor, &header).unwrap()
+        .take(3)
+        .map(|x| x.unwrap())
+        .collect();
+    assert_eq!(first, vec![1, 0, 1]);
+
+    match read_type1000_data::<i8, _>(&mut cursor, &header) {
+        Err(Error::DataTypeMismatchError) => {},
+        _ => panic!("Reading packed bits as bytes should have produced an error"),
+    }
+}
+
+#[test]
+fn read_packed_nibbles_test() {
+    let mut cursor = Cursor::new(type1000_bytes(b"SN", &[0x3f, 0xa0], Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    let nibbles = read_packed_data(&mut cursor, &header).unwrap();
+    assert_eq!(nibbles, vec![3, 15, 10, 0]);
+}
+
+#[test]
+fn read_packed_mismatch_test() {
+    let mut cursor = Cursor::new(type1000_bytes(b"SB", &[1, 2], Endianness::Little));
+    let header = read_header(&mut cursor).unwrap();
+
+    match read_packed_data(&mut cursor, &header) {
+        Err(Error::DataTypeMismatchError) => {},
+        _ => panic!("Reading byte data as packed bits should have produced an error"),
+    }
+}
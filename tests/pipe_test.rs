@@ -0,0 +1,138 @@
+use std::env::temp_dir;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::remove_file;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bluefile::{
+    Bluefile,
+    DataType,
+    Error,
+    Header,
+    header_to_bytes,
+    PipeReader,
+    read_header,
+};
+
+const PIPE_SIZE: usize = 16;
+
+fn pipe_header() -> Header {
+    Header{
+        pipe: 1,
+        data_size: PIPE_SIZE as f64,
+        data_type: DataType{rank: b'S', format: b'L'},
+        outlets: 1,
+        pipesize: PIPE_SIZE as i32,
+        ..Header::default()
+    }
+}
+
+/// Writes samples into the circular buffer the way a producer would, then publishes them by
+/// updating in_byte.
+fn produce(path: &Path, header: &mut Header, samples: &[i32]) {
+    let mut file = OpenOptions::new().write(true).open(path).unwrap();
+
+    for x in samples {
+        let offset = (header.in_byte as usize) % PIPE_SIZE;
+        file.seek(SeekFrom::Start(512 + offset as u64)).unwrap();
+        file.write_all(&x.to_le_bytes()).unwrap();
+        header.in_byte += 4.0;
+    }
+
+    file.seek(SeekFrom::Start(0)).unwrap();
+    file.write_all(&header_to_bytes(header).unwrap()).unwrap();
+}
+
+fn create_pipe(name: &str) -> (PathBuf, Header) {
+    let path = temp_dir().join(format!("bluefile_{}_{}.tmp", name, std::process::id()));
+    let header = pipe_header();
+    let mut file = File::create(&path).unwrap();
+    file.write_all(&header_to_bytes(&header).unwrap()).unwrap();
+    file.write_all(&[0_u8; 256 + PIPE_SIZE]).unwrap();
+    (path, header)
+}
+
+#[test]
+fn read_pipe_test() {
+    let (path, mut producer) = create_pipe("pipe_test");
+    let file = File::open(&path).unwrap();
+    let header = read_header(&file).unwrap();
+    let mut pipe = PipeReader::new(&file, &header).unwrap();
+    pipe.set_poll_interval(Duration::from_millis(1));
+
+    produce(&path, &mut producer, &[0, 1, 2]);
+    assert_eq!(pipe.read_samples::<i32>(3, Some(Duration::from_millis(100))).unwrap(), vec![0, 1, 2]);
+
+    // wraps around the end of the buffer
+    produce(&path, &mut producer, &[3, 4, 5]);
+    assert_eq!(pipe.read_samples::<i32>(3, Some(Duration::from_millis(100))).unwrap(), vec![3, 4, 5]);
+    assert_eq!(pipe.position(), 24);
+
+    match pipe.read_samples::<i32>(1, Some(Duration::from_millis(20))) {
+        Err(Error::PipeTimeoutError) => {},
+        _ => panic!("Reading past the producer should have timed out"),
+    }
+
+    match pipe.read_samples::<i32>(5, Some(Duration::from_millis(20))) {
+        Err(Error::PipeReadTooLargeError(20)) => {},
+        _ => panic!("Reading more than the buffer holds should have produced an error"),
+    }
+
+    match pipe.read_samples::<i32>(usize::MAX / 2, Some(Duration::from_millis(20))) {
+        Err(Error::PipeReadTooLargeError(_)) => {},
+        _ => panic!("Reading more samples than fit in memory should have produced an error"),
+    }
+
+    // the producer laps the reader
+    produce(&path, &mut producer, &[6, 7, 8, 9, 10]);
+    match pipe.read_available() {
+        Err(Error::PipeOverrunError(4)) => {},
+        _ => panic!("Falling more than a buffer behind should have produced an error"),
+    }
+    assert_eq!(pipe.read_available().unwrap().len(), 16);
+
+    produce(&path, &mut producer, &[11, 12]);
+    producer.pipe = 0;
+    produce(&path, &mut producer, &[]);
+
+    let rest: Vec<i32> = pipe.samples().map(|x| x.unwrap()).collect();
+    assert_eq!(rest, vec![11, 12]);
+    assert!(pipe.is_closed());
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn not_a_pipe_test() {
+    let (path, mut producer) = create_pipe("not_a_pipe_test");
+    producer.pipe = 0;
+    produce(&path, &mut producer, &[]);
+
+    let mut bluefile = Bluefile::open(&path).unwrap();
+    match bluefile.pipe_reader() {
+        Err(Error::NotAPipeError) => {},
+        _ => panic!("Opening a regular file as a pipe should have produced an error"),
+    }
+
+    remove_file(&path).unwrap();
+}
+
+#[test]
+fn pipe_detached_test() {
+    let (path, _) = create_pipe("pipe_detached_test");
+
+    // the buffer of a detached header is not in the header file
+    let file = File::open(&path).unwrap();
+    let detached = Header{detached: 1, ..read_header(&file).unwrap()};
+    match PipeReader::new(&file, &detached) {
+        Err(Error::DetachedDataError) => {},
+        _ => panic!("Opening a detached header as a pipe should have produced an error"),
+    }
+
+    remove_file(&path).unwrap();
+}
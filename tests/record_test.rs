@@ -0,0 +1,187 @@
+mod common;
+
+use std::io::Cursor;
+
+use bluefile::{
+    Adjunct,
+    adjunct_to_bytes,
+    Bluefile,
+    DataType,
+    Endianness,
+    Error,
+    Header,
+    read_adjunct,
+    read_header,
+    read_type6000_subrecords,
+    RecordReader,
+    Value,
+};
+
+use common::{
+    header,
+    header_bytes,
+};
+
+fn type3000_bytes() -> Vec<u8> {
+    let mut v = header_bytes(&Header{data_size: 48.0, ..header(3000, b"NH")});
+
+    // adjunct: 3 subrecords in a 16 byte record
+    v[276..280].copy_from_slice(&3_i32.to_le_bytes());
+    v[300..304].copy_from_slice(&16_i32.to_le_bytes());
+    v[304..312].copy_from_slice(b"TIMESD\x00\x00");
+    v[312..318].copy_from_slice(b"AMP SF");
+    v[318..320].copy_from_slice(&8_i16.to_le_bytes());
+    v[320..326].copy_from_slice(b"ID  SL");
+    v[326..328].copy_from_slice(&12_i16.to_le_bytes());
+
+    for i in 0..3_i32 {
+        v.extend_from_slice(&(i as f64 * 0.5).to_le_bytes());
+        v.extend_from_slice(&(i as f32 * 2.0).to_le_bytes());
+        v.extend_from_slice(&(100 + i).to_le_bytes());
+    }
+
+    v
+}
+
+#[test]
+fn read_type3000_adjunct_test() {
+    let mut cursor = Cursor::new(type3000_bytes());
+    let header = read_header(&mut cursor).unwrap();
+
+    let adjunct = match read_adjunct(&mut cursor, &header).unwrap() {
+        Adjunct::Type3000(adj) => adj,
+        _ => panic!("Expected a type 3000 adjunct"),
+    };
+
+    assert_eq!(adjunct.subrecords, 3);
+    assert_eq!(adjunct.record_length, 16);
+    assert_eq!(adjunct.subrecord_table.len(), 3);
+    assert_eq!(adjunct.subrecord_table[0].name, "TIME".to_string());
+    assert_eq!(adjunct.subrecord_table[0].data_type, DataType{rank: b'S', format: b'D'});
+    assert_eq!(adjunct.subrecord_table[1].name, "AMP".to_string());
+    assert_eq!(adjunct.subrecord_table[1].offset, 8);
+    assert_eq!(adjunct.subrecord_table[2].name, "ID".to_string());
+    assert_eq!(adjunct.subrecord_table[2].offset, 12);
+
+    let mut reader = RecordReader::from_type3000(&mut cursor, &header, &adjunct).unwrap();
+    assert_eq!(reader.num_records(), 3);
+
+    let record = reader.read_record(2).unwrap();
+    assert_eq!(record.get("TIME"), Some(&Value::F64(1.0)));
+    assert_eq!(record.get("AMP"), Some(&Value::F32(4.0)));
+    assert_eq!(record.get("ID").unwrap().as_i64().unwrap(), 102);
+    assert_eq!(record.get("NOPE"), None);
+
+    match reader.read_record(3) {
+        Err(Error::RecordOutOfRangeError(3)) => {},
+        _ => panic!("Reading past the last record should have produced an error"),
+    }
+
+    // the subrecord table only has room for 16 bit offsets
+    let mut far = adjunct.clone();
+    far.subrecord_table[2].offset = 40000;
+    match adjunct_to_bytes(&Adjunct::Type3000(far), Endianness::Little) {
+        Err(Error::InvalidSubrecordError(name)) => assert_eq!(name, "ID"),
+        _ => panic!("A subrecord offset past 32767 should have produced an error"),
+    }
+}
+
+#[test]
+fn read_type3000_records_test() {
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type3000_bytes())).unwrap();
+    let mut reader = bluefile.record_reader().unwrap();
+
+    let records: Vec<_> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[1].get("TIME").unwrap().as_f64().unwrap(), 0.5);
+    assert_eq!(records, reader.read_records(0..3).unwrap());
+}
+
+fn type6000_subrecord(name: &str, format: &[u8; 2], offset: i32, num_elts: i32) -> Vec<u8> {
+    let mut v = vec![b' '; 24];
+    v[0..name.len()].copy_from_slice(name.as_bytes());
+    v.extend_from_slice(&0.0_f64.to_le_bytes());
+    v.extend_from_slice(&0.0_f64.to_le_bytes());
+    v.extend_from_slice(&offset.to_le_bytes());
+    v.extend_from_slice(&num_elts.to_le_bytes());
+    v.extend_from_slice(&0_i32.to_le_bytes());
+    v.extend_from_slice(format);
+    v.extend_from_slice(b"  ");
+    v.extend_from_slice(&[0_u8; 16]);
+    v
+}
+
+fn type6000_bytes() -> Vec<u8> {
+    let mut v = header_bytes(&Header{
+        ext_start: 1024,
+        ext_size: 216,
+        data_size: 72.0,
+        ..header(6000, b"NH")
+    });
+
+    // adjunct: 3 subrecords in a 36 byte record, described at the start of the extended header
+    v[276..280].copy_from_slice(&3_i32.to_le_bytes());
+    v[300..304].copy_from_slice(&36_i32.to_le_bytes());
+    v[304..308].copy_from_slice(&0_i32.to_le_bytes());
+
+    for (i, name) in [b"TRACK1  ", b"TRACK2  "].iter().enumerate() {
+        v.extend_from_slice(*name);
+
+        for p in [1.0_f64, 2.0, 3.0] {
+            v.extend_from_slice(&(p + i as f64).to_le_bytes());
+        }
+
+        v.extend_from_slice(&(7 + i as i32).to_le_bytes());
+    }
+
+    v.resize(1024, 0);
+    v.extend(type6000_subrecord("NAME", b"SA", 0, 8));
+    v.extend(type6000_subrecord("POSITION", b"SD", 8, 3));
+    v.extend(type6000_subrecord("ID", b"SL", 32, 1));
+    v
+}
+
+#[test]
+fn read_type6000_subrecords_test() {
+    let mut cursor = Cursor::new(type6000_bytes());
+    let header = read_header(&mut cursor).unwrap();
+
+    let adjunct = match read_adjunct(&mut cursor, &header).unwrap() {
+        Adjunct::Type6000(adj) => adj,
+        _ => panic!("Expected a type 6000 adjunct"),
+    };
+
+    let subrecords = read_type6000_subrecords(&mut cursor, &header, &adjunct).unwrap();
+    assert_eq!(subrecords.len(), 3);
+    assert_eq!(subrecords[1].name, "POSITION".to_string());
+    assert_eq!(subrecords[1].data_type, DataType{rank: b'S', format: b'D'});
+    assert_eq!(subrecords[1].offset, 8);
+    assert_eq!(subrecords[1].num_elts, 3);
+
+    // more descriptions than the extended header holds
+    let mut corrupt = adjunct.clone();
+    corrupt.subrecords = 4;
+    match read_type6000_subrecords(&mut cursor, &header, &corrupt) {
+        Err(Error::InvalidSubrecordCount(4)) => {},
+        _ => panic!("A subrecord count past the extended header should have produced an error"),
+    }
+
+    corrupt.subrecords = i32::MAX;
+    match read_type6000_subrecords(&mut cursor, &header, &corrupt) {
+        Err(Error::InvalidSubrecordCount(_)) => {},
+        _ => panic!("A huge subrecord count should have produced an error"),
+    }
+}
+
+#[test]
+fn read_type6000_records_test() {
+    let mut bluefile = Bluefile::from_reader(Cursor::new(type6000_bytes())).unwrap();
+    let mut reader = bluefile.record_reader().unwrap();
+    assert_eq!(reader.num_records(), 2);
+    assert_eq!(reader.subrecords()[0].num_bytes().unwrap(), 8);
+
+    let record = reader.read_record(1).unwrap();
+    assert_eq!(record.get("NAME").unwrap().as_str().unwrap(), "TRACK2");
+    assert_eq!(record.get("POSITION"), Some(&Value::Array(vec![Value::F64(2.0), Value::F64(3.0), Value::F64(4.0)])));
+    assert_eq!(record.get("ID"), Some(&Value::I32(8)));
+}
@@ -0,0 +1,225 @@
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use num::complex::Complex;
+
+use bluefile::{
+    Adjunct,
+    Bluefile,
+    Endianness,
+    Error,
+    ExtKeyword,
+    ExtKeywordValue,
+    Header,
+    read_time_range,
+    read_timestamp_range,
+    Timestamp,
+    Type1000Adjunct,
+    Type2000Adjunct,
+    Units,
+    write_bluefile,
+};
+
+use common::header;
+
+fn timed_header(type_code: i32, timecode: f64) -> Header {
+    Header{timecode, ..header(type_code, b"SI")}
+}
+
+fn create_bluefile(header: &Header, adjunct: &Adjunct, samples: &[i16], keywords: &[ExtKeyword]) -> Bluefile<Cursor<Vec<u8>>> {
+    let data: Vec<u8> = samples.iter().flat_map(|x| x.to_le_bytes()).collect();
+    let mut buf = Cursor::new(vec![]);
+    write_bluefile(&mut buf, header, adjunct, &data, keywords).unwrap();
+    buf.set_position(0);
+    Bluefile::from_reader(buf).unwrap()
+}
+
+#[test]
+fn type1000_time_test() {
+    let header = timed_header(1000, 1000.0);
+    let adjunct = Type1000Adjunct{xstart: 0.5, xdelta: 0.25, xunits: 1};
+    let samples: Vec<i16> = (0..10).collect();
+    let mut bluefile = create_bluefile(&header, &Adjunct::Type1000(adjunct), &samples, &[]);
+    let header = bluefile.header().clone();
+    let adjunct = bluefile.type1000_adjunct().unwrap().clone();
+
+    assert_eq!(adjunct.sample_time(&header, 0).unwrap(), 1000.5);
+    assert_eq!(adjunct.sample_time(&header, 4).unwrap(), 1001.5);
+    assert_eq!(adjunct.sample_at_time(&header, 1000.5).unwrap(), 0);
+    assert_eq!(adjunct.sample_at_time(&header, 1001.6).unwrap(), 4);
+    assert_eq!(adjunct.sample_at_time(&header, 1002.99).unwrap(), 9);
+
+    match adjunct.sample_at_time(&header, 1000.4) {
+        Err(Error::TimeOutOfRangeError(_)) => {},
+        _ => panic!("A time before the first sample should be out of range"),
+    }
+
+    match adjunct.sample_at_time(&header, 1003.0) {
+        Err(Error::TimeOutOfRangeError(_)) => {},
+        _ => panic!("A time after the last sample should be out of range"),
+    }
+
+    assert_eq!(adjunct.samples_in_time_range(&header, 1001.0, 1001.5).unwrap(), 2..4);
+    assert_eq!(adjunct.samples_in_time_range(&header, 1001.1, 1001.6).unwrap(), 3..5);
+    assert_eq!(adjunct.samples_in_time_range(&header, 0.0, 5000.0).unwrap(), 0..10);
+    assert_eq!(adjunct.samples_in_time_range(&header, 2000.0, 3000.0).unwrap(), 10..10);
+    assert_eq!(adjunct.samples_in_time_range(&header, 1001.5, 1001.0).unwrap(), 4..4);
+
+    assert_eq!(bluefile.read_time_range::<i16>(1001.0, 1001.5).unwrap(), vec![2, 3]);
+    assert_eq!(bluefile.read_time_range::<i16>(1002.0, 1010.0).unwrap(), vec![6, 7, 8, 9]);
+    assert_eq!(bluefile.read_time_range::<i16>(0.0, 1.0).unwrap(), vec![]);
+
+    match bluefile.read_time_range::<f32>(1001.0, 1001.5) {
+        Err(Error::DataTypeMismatchError) => {},
+        _ => panic!("Reading the wrong sample type should fail"),
+    }
+}
+
+#[test]
+fn type2000_time_test() {
+    let header = timed_header(2000, 100.0);
+    let adjunct = Type2000Adjunct{
+        xstart: 0.0,
+        xdelta: 1.0,
+        xunits: 0,
+        subsize: 2,
+        ystart: 10.0,
+        ydelta: 2.0,
+        yunits: 1,
+    };
+    let samples: Vec<i16> = (0..8).collect();
+    let mut bluefile = create_bluefile(&header, &Adjunct::Type2000(adjunct.clone()), &samples, &[]);
+    let header = bluefile.header().clone();
+
+    assert_eq!(adjunct.frame_time(&header, 3).unwrap(), 116.0);
+    assert_eq!(adjunct.frame_at_time(&header, 113.0).unwrap(), 1);
+    assert_eq!(adjunct.frames_in_time_range(&header, 111.0, 116.0).unwrap(), 1..3);
+
+    match adjunct.frame_at_time(&header, 118.0) {
+        Err(Error::TimeOutOfRangeError(_)) => {},
+        _ => panic!("A time after the last frame should be out of range"),
+    }
+
+    assert_eq!(bluefile.read_frame_time_range::<i16>(112.0, 120.0).unwrap(), vec![vec![2, 3], vec![4, 5], vec![6, 7]]);
+
+    let bad_delta = Type2000Adjunct{ydelta: 0.0, ..adjunct};
+    match bad_delta.frame_at_time(&header, 110.0) {
+        Err(Error::InvalidTimeDeltaError(_)) => {},
+        _ => panic!("A zero ydelta should be rejected"),
+    }
+}
+
+#[test]
+fn read_time_range_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/pulse_cx.tmp");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    let header = bluefile.header().clone();
+    let adjunct = bluefile.type1000_adjunct().unwrap().clone();
+
+    let all: Vec<Complex<f32>> = bluefile.read_type1000_data().unwrap();
+    let t0 = header.timecode + 100.0;
+    let window: Vec<Complex<f32>> = read_time_range(bluefile.into_inner(), &header, &adjunct, t0, t0 + 10.0).unwrap();
+    assert_eq!(window, all[100..110].to_vec());
+}
+
+#[test]
+fn not_time_axis_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/sin.tmp");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    let header = bluefile.header().clone();
+    let adjunct = bluefile.type1000_adjunct().unwrap().clone();
+    assert_eq!(adjunct.xunits, 0);
+
+    match adjunct.sample_at_time(&header, header.timecode) {
+        Err(Error::NotTimeAxisError(Units::None)) => {},
+        _ => panic!("An x axis without time units should be rejected"),
+    }
+
+    let frequency = Type2000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 1, subsize: 2, ystart: 0.0, ydelta: 1.0, yunits: 3};
+    let header = timed_header(2000, 0.0);
+    match frequency.frames_in_time_range(&header, 0.0, 1.0) {
+        Err(Error::NotTimeAxisError(Units::Frequency)) => {},
+        _ => panic!("A y axis in Hz should be rejected"),
+    }
+}
+
+#[test]
+fn large_timecode_test() {
+    // Near 2.3e9 seconds an f64 only resolves about 4.8e-7 seconds, so adding the timecode to
+    // xstart before dividing by xdelta would land several samples off.
+    let timecode = 2.3e9 + 0.25;
+    let ulp = 2_f64.powi(-21);
+    let header = timed_header(1000, timecode);
+    let adjunct = Type1000Adjunct{xstart: 3e-7, xdelta: 1e-7, xunits: 1};
+    let samples: Vec<i16> = (0..10).collect();
+    let mut bluefile = create_bluefile(&header, &Adjunct::Type1000(adjunct), &samples, &[]);
+    let header = bluefile.header().clone();
+    let adjunct = bluefile.type1000_adjunct().unwrap().clone();
+
+    assert_eq!(adjunct.sample_at_time(&header, timecode + ulp).unwrap(), 1);
+    assert_eq!(adjunct.samples_in_time_range(&header, timecode + ulp, timecode + 2.0*ulp).unwrap(), 2..7);
+    assert_eq!(bluefile.read_time_range::<i16>(timecode + ulp, timecode + 2.0*ulp).unwrap(), vec![2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn tc_prec_time_test() {
+    // TC_PREC carries the timecode below what an f64 near 2.3e9 seconds can hold, and the samples
+    // are 10 ns apart, so only timestamps can pick them out.
+    let header = timed_header(1000, 2.3e9 + 0.25);
+    let adjunct = Type1000Adjunct{xstart: 0.0, xdelta: 1e-8, xunits: 1};
+    let keywords = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_f64(2.5e-9, Endianness::Little))];
+    let samples: Vec<i16> = (0..10).collect();
+    let mut bluefile = create_bluefile(&header, &Adjunct::Type1000(adjunct), &samples, &keywords);
+    let header = bluefile.header().clone();
+    let adjunct = bluefile.type1000_adjunct().unwrap().clone();
+
+    let start = bluefile.timestamp();
+    assert_eq!(start, Timestamp::new(2_300_000_000, 0.25 + 2.5e-9));
+    assert_eq!(adjunct.sample_timestamp(&header, &keywords, 0).unwrap(), start);
+    assert!((adjunct.sample_timestamp(&header, &keywords, 4).unwrap().seconds_since(&start) - 4e-8).abs() < 1e-15);
+
+    assert_eq!(adjunct.sample_at_timestamp(&header, &keywords, start.add_seconds(3.5e-8)).unwrap(), 3);
+    match adjunct.sample_at_timestamp(&header, &keywords, start.add_seconds(-1e-9)) {
+        Err(Error::TimeOutOfRangeError(_)) => {},
+        _ => panic!("A timestamp before the first sample should be out of range"),
+    }
+
+    let t0 = start.add_seconds(2e-8);
+    let t1 = start.add_seconds(5e-8);
+    assert_eq!(adjunct.samples_in_timestamp_range(&header, &keywords, t0, t1).unwrap(), 2..5);
+    assert_eq!(bluefile.read_timestamp_range::<i16>(t0, t1).unwrap(), vec![2, 3, 4]);
+
+    let window: Vec<i16> = read_timestamp_range(bluefile.into_inner(), &header, &keywords, &adjunct, t0, t1).unwrap();
+    assert_eq!(window, vec![2, 3, 4]);
+
+    // without the extended header the start time is off by TC_PREC, so a damaged one is an error
+    let mut v = vec![];
+    let data: Vec<u8> = samples.iter().flat_map(|x| x.to_le_bytes()).collect();
+    let written = write_bluefile(&mut v, &header, &Adjunct::Type1000(adjunct), &data, &keywords).unwrap();
+    v[written.ext_start..written.ext_start+4].copy_from_slice(&2_i32.to_le_bytes());
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+    match bluefile.read_timestamp_range::<i16>(t0, t1) {
+        Err(Error::ExtHeaderKeywordLengthParseError) => {},
+        _ => panic!("A damaged extended header should have produced an error"),
+    }
+}
+
+#[test]
+fn tc_prec_frame_time_test() {
+    let header = timed_header(2000, 2.3e9);
+    let adjunct = Type2000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 0, subsize: 2, ystart: 0.0, ydelta: 1e-7, yunits: 1};
+    let keywords = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_f64(5e-8, Endianness::Little))];
+    let samples: Vec<i16> = (0..8).collect();
+    let mut bluefile = create_bluefile(&header, &Adjunct::Type2000(adjunct.clone()), &samples, &keywords);
+    let header = bluefile.header().clone();
+
+    let start = Timestamp::new(2_300_000_000, 5e-8);
+    assert_eq!(adjunct.frame_timestamp(&header, &keywords, 0).unwrap(), start);
+    assert_eq!(adjunct.frame_at_timestamp(&header, &keywords, start.add_seconds(2.5e-7)).unwrap(), 2);
+    assert_eq!(adjunct.frames_in_timestamp_range(&header, &keywords, start.add_seconds(1e-7), start.add_seconds(1.0)).unwrap(), 1..4);
+    assert_eq!(bluefile.read_frame_timestamp_range::<i16>(start.add_seconds(1e-7), start.add_seconds(3e-7)).unwrap(), vec![vec![2, 3], vec![4, 5]]);
+}
@@ -0,0 +1,140 @@
+use std::io::Cursor;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use bluefile::{
+    Adjunct,
+    Bluefile,
+    Endianness,
+    Error,
+    ExtKeyword,
+    ExtKeywordValue,
+    Header,
+    Timestamp,
+    Type1000Adjunct,
+    write_bluefile,
+};
+
+#[test]
+fn j1950_unix_test() {
+    let epoch = Timestamp::from_j1950(0.0);
+    assert_eq!(epoch.to_iso8601(), "1950-01-01T00:00:00Z");
+    assert_eq!(epoch.unix_seconds(), -631152000.0);
+
+    let unix = Timestamp::from_unix(0.0);
+    assert_eq!(unix.j1950_seconds(), 631152000.0);
+    assert_eq!(unix.to_string(), "1970-01-01T00:00:00Z");
+
+    let t = Timestamp::from_j1950(-0.25);
+    assert_eq!(t.seconds(), -1);
+    assert_eq!(t.fraction(), 0.75);
+    assert_eq!(t.to_iso8601(), "1949-12-31T23:59:59.75Z");
+}
+
+#[test]
+fn iso8601_test() {
+    let t = Timestamp::from_iso8601("2024-02-29T12:30:15.125Z").unwrap();
+    assert_eq!(t.unix_seconds(), 1709209815.125);
+    assert_eq!(t.to_iso8601(), "2024-02-29T12:30:15.125Z");
+
+    let t: Timestamp = "2000-01-01 00:00:00.000000000001+00:00".parse().unwrap();
+    assert_eq!(t.seconds(), 1577836800);
+    assert_eq!(t.to_iso8601(), "2000-01-01T00:00:00.000000000001Z");
+
+    for bad in ["2023-02-29T00:00:00Z", "2024-01-01", "2024-01-01T24:00:00Z", "2024-01-01T00:00:00.Z", "yesterday"] {
+        match Timestamp::from_iso8601(bad) {
+            Err(Error::TimestampParseError(_)) => {},
+            _ => panic!("{} should not parse", bad),
+        }
+    }
+}
+
+#[test]
+fn system_time_test() {
+    let now = SystemTime::now();
+    assert_eq!(SystemTime::from(Timestamp::from(now)), now);
+
+    let before_unix = UNIX_EPOCH - Duration::new(10, 500_000_000);
+    let t = Timestamp::from(before_unix);
+    assert_eq!(t.unix_seconds(), -10.5);
+    assert_eq!(SystemTime::from(t), before_unix);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_test() {
+    let t = Timestamp::from_iso8601("2021-06-01T08:00:00.5Z").unwrap();
+    let dt = chrono::DateTime::<chrono::Utc>::try_from(t).unwrap();
+    assert_eq!(dt.to_rfc3339(), "2021-06-01T08:00:00.500+00:00");
+    assert_eq!(Timestamp::from(dt), t);
+}
+
+#[test]
+fn tc_prec_test() {
+    let header = Header{timecode: 2323555200.5, ..Header::default()};
+
+    let adjunct = Adjunct::Type1000(Type1000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 1});
+    let keywords = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_f64(2.5e-10, Endianness::Little))];
+    let mut buf = Cursor::new(vec![]);
+    write_bluefile(&mut buf, &header, &adjunct, &[0_u8; 8], &keywords).unwrap();
+    buf.set_position(0);
+
+    let mut bluefile = Bluefile::from_reader(buf).unwrap();
+    let t = bluefile.timestamp();
+    assert_eq!(t.seconds(), 2323555200);
+    assert_eq!(t.to_iso8601(), "2023-08-19T00:00:00.50000000025Z");
+
+    let text = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_string("1e-9", Endianness::Little))];
+    assert_eq!(Timestamp::from_header(&header, &text).unwrap().to_iso8601(), "2023-08-19T00:00:00.500000001Z");
+    assert_eq!(Timestamp::from_header(&header, &[]).unwrap().to_iso8601(), "2023-08-19T00:00:00.5Z");
+}
+
+#[test]
+fn timestamp_fallback_test() {
+    let header = Header{timecode: 2323555200.5, ..Header::default()};
+
+    let adjunct = Adjunct::Type1000(Type1000Adjunct{xstart: 0.0, xdelta: 1.0, xunits: 1});
+    let keywords = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_f64(2.5e-10, Endianness::Little))];
+    let mut v = vec![];
+    let written = write_bluefile(&mut v, &header, &adjunct, &[0_u8; 8], &keywords).unwrap();
+
+    // a keyword block too short to hold its own header
+    v[written.ext_start..written.ext_start+4].copy_from_slice(&2_i32.to_le_bytes());
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+    assert!(bluefile.ext_header().is_err());
+    assert_eq!(bluefile.timestamp(), Timestamp::from_j1950(2323555200.5));
+
+    let keywords = vec![ExtKeyword::new("TC_PREC", ExtKeywordValue::from_string("soon", Endianness::Little))];
+    let mut v = vec![];
+    write_bluefile(&mut v, &header, &adjunct, &[0_u8; 8], &keywords).unwrap();
+    let mut bluefile = Bluefile::from_reader(Cursor::new(v)).unwrap();
+    assert_eq!(bluefile.timestamp(), Timestamp::from_j1950(2323555200.5));
+}
+
+#[test]
+fn timestamp_arithmetic_test() {
+    let t = Timestamp::new(2_300_000_000, 0.25);
+    assert_eq!(t.add_seconds(1.5), Timestamp::new(2_300_000_001, 0.75));
+    assert_eq!(t.add_seconds(-0.5), Timestamp::new(2_299_999_999, 0.75));
+    assert!((t.add_seconds(2.5e-9).seconds_since(&t) - 2.5e-9).abs() < 1e-16);
+    assert_eq!(t.seconds_since(&Timestamp::new(2_300_000_010, 0.0)), -9.75);
+}
+
+#[test]
+fn extreme_timecode_test() {
+    // corrupt timecodes are clamped rather than overflowing
+    for timecode in [-1e300, 1e300, f64::INFINITY, f64::NEG_INFINITY, f64::NAN] {
+        let header = Header{timecode, ..Header::default()};
+        let t = Timestamp::from_header(&header, &[]).unwrap();
+        assert!(t.to_iso8601().ends_with('Z'));
+        assert!(t.unix_seconds().is_finite());
+        assert!(t.add_seconds(-1e300).add_seconds(1e300).to_iso8601().ends_with('Z'));
+        assert_eq!(Timestamp::from(SystemTime::from(t)), t);
+    }
+
+    let t = Timestamp::from_j1950(-1e300);
+    assert_eq!(t, Timestamp::from_j1950(-2e300));
+    assert!(t.seconds() < 0);
+    assert_eq!(Timestamp::from_j1950(f64::NAN), Timestamp::from_j1950(0.0));
+}
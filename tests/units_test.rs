@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+use bluefile::{
+    Bluefile,
+    Units,
+};
+
+#[test]
+fn units_codes_test() {
+    assert_eq!(Units::from(3), Units::Frequency);
+    assert_eq!(Units::from(63), Units::AltitudeMeters);
+    assert_eq!(Units::from(39), Units::Unknown(39));
+    assert_eq!(i32::from(Units::DistanceFeet), 40);
+    assert_eq!(Units::Unknown(99).code(), 99);
+
+    for code in -1..70 {
+        assert_eq!(Units::from(code).code(), code);
+    }
+}
+
+#[test]
+fn units_names_test() {
+    assert_eq!(Units::Frequency.name(), "Frequency");
+    assert_eq!(Units::Frequency.abbreviation(), "Hz");
+    assert_eq!(Units::Frequency.to_string(), "Frequency (Hz)");
+    assert_eq!(Units::RelativePowerMilliwatts.to_string(), "Relative power (dBm)");
+    assert_eq!(Units::None.to_string(), "None");
+    assert_eq!(Units::Unknown(99).to_string(), "Unknown");
+}
+
+#[test]
+fn adjunct_units_test() {
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/pulse_cx.tmp");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    assert_eq!(bluefile.type1000_adjunct().unwrap().x_units(), Units::Time);
+
+    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    d.push("resources/test/penny.prm");
+    let mut bluefile = Bluefile::open(&d).unwrap();
+    let adjunct = bluefile.type2000_adjunct().unwrap();
+    assert_eq!(adjunct.x_units(), Units::None);
+    assert_eq!(adjunct.y_units(), Units::None);
+}
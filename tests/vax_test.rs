@@ -0,0 +1,65 @@
+use std::io::Cursor;
+
+use bluefile::{
+    bytes_to_f32,
+    bytes_to_f64,
+    bytes_to_i32,
+    Endianness,
+    f32_to_bytes,
+    f64_to_bytes,
+    read_header,
+    read_type1000_data,
+};
+
+#[test]
+fn vax_endianness_test() {
+    assert_eq!(Endianness::try_from(&b"VAXD"[..]).unwrap(), Endianness::VaxD);
+    assert_eq!(Endianness::try_from(&b"VAXG"[..]).unwrap(), Endianness::VaxG);
+    assert_eq!(Endianness::VaxG.as_bytes(), b"VAXG");
+    assert_eq!(bytes_to_i32(&[1, 2, 0, 0], Endianness::VaxD).unwrap(), 513);
+}
+
+#[test]
+fn vax_float_test() {
+    assert_eq!(bytes_to_f32(&[0x80, 0x40, 0x00, 0x00], Endianness::VaxD).unwrap(), 1.0);
+    assert_eq!(bytes_to_f32(&[0x20, 0xc1, 0x00, 0x00], Endianness::VaxG).unwrap(), -2.5);
+    assert_eq!(bytes_to_f32(&[0x00, 0x00, 0x00, 0x00], Endianness::VaxD).unwrap(), 0.0);
+    assert!(bytes_to_f32(&[0x00, 0x80, 0x00, 0x00], Endianness::VaxD).unwrap().is_nan());
+
+    assert_eq!(bytes_to_f64(&[0x80, 0x40, 0, 0, 0, 0, 0, 0], Endianness::VaxD).unwrap(), 1.0);
+    assert_eq!(bytes_to_f64(&[0x10, 0x40, 0, 0, 0, 0, 0, 0], Endianness::VaxG).unwrap(), 1.0);
+
+    assert_eq!(f32_to_bytes(-2.5, Endianness::VaxD), [0x20, 0xc1, 0x00, 0x00]);
+    assert_eq!(f64_to_bytes(1.0, Endianness::VaxG), [0x10, 0x40, 0, 0, 0, 0, 0, 0]);
+
+    for x in [0.1, -3.75e12, 6.02e23, 1.0e-30, std::f64::consts::PI] {
+        for endianness in [Endianness::VaxD, Endianness::VaxG] {
+            assert_eq!(bytes_to_f64(&f64_to_bytes(x, endianness), endianness).unwrap(), x);
+            assert_eq!(bytes_to_f32(&f32_to_bytes(x as f32, endianness), endianness).unwrap(), x as f32);
+        }
+    }
+}
+
+#[test]
+fn read_vaxd_file_test() {
+    let mut v = vec![0_u8; 512];
+    v[0..4].copy_from_slice(b"BLUE");
+    v[4..8].copy_from_slice(b"VAXD");
+    v[8..12].copy_from_slice(b"VAXD");
+    v[32..40].copy_from_slice(&f64_to_bytes(512.0, Endianness::VaxD));
+    v[40..48].copy_from_slice(&f64_to_bytes(12.0, Endianness::VaxD));
+    v[48..52].copy_from_slice(&1000_i32.to_le_bytes());
+    v[52..54].copy_from_slice(b"SF");
+
+    for x in [1.0_f32, -2.5, 0.0] {
+        v.extend_from_slice(&f32_to_bytes(x, Endianness::VaxD));
+    }
+
+    let mut cursor = Cursor::new(v);
+    let header = read_header(&mut cursor).unwrap();
+    assert_eq!(header.data_endianness, Endianness::VaxD);
+    assert_eq!(header.data_start, 512.0);
+
+    let data: Vec<f32> = read_type1000_data(&mut cursor, &header).unwrap();
+    assert_eq!(data, vec![1.0, -2.5, 0.0]);
+}